@@ -25,72 +25,72 @@ pub enum JsonValue<'a> {
     Object(HashMap<&'a str, JsonValue<'a>>),
 }
 
-fn json_parser<'a>() -> Parsec<'a, JsonValue<'a>> {
-    #[inline]
-    fn spaces<E, A>(p: E) -> FMap<And<Skip, (), E, A>, ((), A), A>
-    where
-        E: Parser<A>,
-    {
-        seq!((skip(" \n\r\t".to_string())) ~> (p))
-    }
+#[inline]
+fn spaces<E, A>(p: E) -> ThenRight<Skip, (), E, A>
+where
+    E: Parser<A>,
+{
+    seq!((skip(" \n\r\t".to_string())) ~> (p))
+}
 
-    fn to_str(s: &[u8]) -> &str {
-        std::str::from_utf8(s).unwrap()
-    }
+fn to_str(s: &[u8]) -> &str {
+    std::str::from_utf8(s).unwrap()
+}
 
-    #[inline]
-    fn object<'a>() -> Parsec<'a, JsonValue<'a>> {
-        let attribute =
-            || seq!((seq!((spaces(delimited_string())) <~ (spaces(':')))) ~ (json::<'a>()));
-        let attributes =
-            seq!((attribute()) ~ (seq!((spaces(',')) ~> (attribute())).optrep())).opt();
-        let parser = seq!(('{') ~> (attributes) <~ (spaces('}'))).fmap(|v| {
-            let mut r = HashMap::default();
-            if let Some(((k, e), v)) = v {
-                r.insert(to_str(k), e);
-                for (k, e) in v {
+// The JSon value rule is referenced by objects and arrays
+
+grammar! {
+    struct Json<'a> {
+        json: JsonValue<'a> = spaces(dispatch(
+            vec![
+                (b"{", parsec!('a, object())),
+                (b"[", parsec!('a, array())),
+                (
+                    b"\"",
+                    parsec!('a, delimited_string().fmap(|v| JsonValue::Str(to_str(v)))),
+                ),
+                (
+                    b"f",
+                    parsec!('a, "false".fmap(|_| JsonValue::Boolean(false))),
+                ),
+                (b"t", parsec!('a, "true".fmap(|_| JsonValue::Boolean(true)))),
+                (b"n", parsec!('a, "null".fmap(|_| JsonValue::Null()))),
+            ],
+            parsec!('a, float().fmap(|v| JsonValue::Num(v.to_f64()))),
+        ));
+        object: JsonValue<'a> = {
+            let attribute = || seq!((seq!((spaces(delimited_string())) <~ (spaces(':')))) ~ (json()));
+            let attributes = seq!((attribute()) ~ (seq!((spaces(',')) ~> (attribute())).optrep())).opt();
+
+            seq!(('{') ~> (attributes) <~ (spaces('}'))).fmap(|v| {
+                let mut r = HashMap::default();
+                if let Some(((k, e), v)) = v {
                     r.insert(to_str(k), e);
+                    for (k, e) in v {
+                        r.insert(to_str(k), e);
+                    }
                 }
-            }
-            JsonValue::Object(r)
-        });
-
-        parsec!('a, parser)
-    }
-
-    #[inline]
-    fn array<'a>() -> Parsec<'a, JsonValue<'a>> {
-        let elements =
-            seq!((json::<'a>()) ~ (seq!((spaces(',')) ~> (json::<'a>())).optrep())).opt();
-        let parser = seq!(('[') ~> (elements) <~ (spaces(']'))).fmap(|v| {
-            if let Some((e, v)) = v {
-                let mut r = v;
-                r.insert(0, e);
-                JsonValue::Array(r)
-            } else {
-                JsonValue::Array(Vec::default())
-            }
-        });
-
-        parsec!('a, parser)
-    }
-
-    #[inline]
-    fn json<'a>() -> Parsec<'a, JsonValue<'a>> {
-        let parser = lazy!(spaces(lookahead(any()).bind(|c| match c as char {
-            '{' => object::<'a>(),
-            '[' => array::<'a>(),
-            '"' => parsec!('a, delimited_string().fmap(|v| JsonValue::Str(to_str(v)))),
-            'f' => parsec!('a, "false".fmap(|_| JsonValue::Boolean(false))),
-            't' => parsec!('a, "true".fmap(|_| JsonValue::Boolean(true))),
-            'n' => parsec!('a, "null".fmap(|_| JsonValue::Null())),
-            _ => parsec!('a, float().fmap(|v| JsonValue::Num(v.to_f64()))),
-        })));
-
-        parsec!('a, parser)
+                JsonValue::Object(r)
+            })
+        };
+        array: JsonValue<'a> = {
+            let elements = seq!((json()) ~ (seq!((spaces(',')) ~> (json())).optrep())).opt();
+
+            seq!(('[') ~> (elements) <~ (spaces(']'))).fmap(|v| {
+                if let Some((e, v)) = v {
+                    let mut r = v;
+                    r.insert(0, e);
+                    JsonValue::Array(r)
+                } else {
+                    JsonValue::Array(Vec::default())
+                }
+            })
+        };
     }
+}
 
-    parsec!('a,json::<'a>().then_left(spaces(eos())))
+fn json_parser<'a>() -> Parsec<'a, JsonValue<'a>> {
+    parsec!('a, Json::new().json().clone().then_left(spaces(eos())))
 }
 
 // -------------------------------------------------------------------------------------------------
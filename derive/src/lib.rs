@@ -0,0 +1,340 @@
+extern crate proc_macro;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse as _, ParseStream};
+use syn::{Attribute, Data, DeriveInput, Expr, Fields, Ident, LitInt, LitStr, Path, Token};
+
+// -------------------------------------------------------------------------------------------------
+// Derive entry point
+// -------------------------------------------------------------------------------------------------
+
+// A struct is parsed as the sequence of its fields and an enum as the ordered choice of its
+// variants. A `#[parse(..)]` pattern made of string literals and field names, or tuple indexes,
+// replaces the default sequence.
+
+#[proc_macro_derive(Parse, attributes(parse))]
+pub fn derive_parse(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Parse cannot be derived for generic types",
+        ));
+    }
+
+    let name = &input.ident;
+    let container = Container::from(&input.attrs)?;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let path = syn::parse_quote!(#name);
+            build(&path, &data.fields, &container, &input.attrs)?
+        }
+        Data::Enum(data) => {
+            let mut choice: Option<TokenStream> = None;
+
+            for variant in &data.variants {
+                let variant_name = &variant.ident;
+                let path = syn::parse_quote!(#name::#variant_name);
+                let parser = build(&path, &variant.fields, &container, &variant.attrs)?;
+                let parser = quote!(do_try(#parser));
+
+                choice = Some(match choice {
+                    None => parser,
+                    Some(choice) => quote!(#choice.or(#parser)),
+                });
+            }
+
+            choice.ok_or_else(|| {
+                syn::Error::new_spanned(name, "Parse cannot be derived for empty enums")
+            })?
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Parse cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl<'a> ::parsecute::parsers::derive::Parse<'a> for #name {
+            fn parser() -> ::parsecute::parsers::core::Parsec<'a, Self> {
+                #[allow(unused_imports)]
+                use ::parsecute::parsers::basic::*;
+                #[allow(unused_imports)]
+                use ::parsecute::parsers::flow::*;
+                #[allow(unused_imports)]
+                use ::parsecute::parsers::monadic::*;
+
+                ::parsecute::parsers::core::Parsec(Box::new(#body))
+            }
+        }
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+// Attributes
+// -------------------------------------------------------------------------------------------------
+
+// Options given at the type level
+struct Container {
+    whitespace: Option<LitStr>,
+}
+
+impl Container {
+    fn from(attrs: &[Attribute]) -> syn::Result<Container> {
+        let mut whitespace = None;
+
+        for attr in parse_attrs(attrs) {
+            let arg = attr.parse_args_with(|input: ParseStream| {
+                if is_option(input) {
+                    Arg::parse_option(input).map(Some)
+                } else {
+                    input.parse::<TokenStream>().map(|_| None)
+                }
+            })?;
+
+            match arg {
+                Some(Arg::Whitespace(lit)) => whitespace = Some(lit),
+                Some(Arg::With(expr)) => {
+                    return Err(syn::Error::new_spanned(
+                        expr,
+                        "with is only allowed on fields",
+                    ))
+                }
+                Some(Arg::Sep(lit)) => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "sep is only allowed on fields",
+                    ))
+                }
+                None => (),
+            }
+        }
+
+        Ok(Container { whitespace })
+    }
+}
+
+// Options given at the field level
+struct Field {
+    with: Option<Expr>,
+    sep: Option<LitStr>,
+}
+
+impl Field {
+    fn from(attrs: &[Attribute]) -> syn::Result<Field> {
+        let mut field = Field {
+            with: None,
+            sep: None,
+        };
+
+        for attr in parse_attrs(attrs) {
+            match attr.parse_args_with(Arg::parse_option)? {
+                Arg::With(expr) => field.with = Some(expr),
+                Arg::Sep(lit) => field.sep = Some(lit),
+                Arg::Whitespace(lit) => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "whitespace is only allowed on types",
+                    ))
+                }
+            }
+        }
+
+        Ok(field)
+    }
+}
+
+enum Arg {
+    Whitespace(LitStr),
+    With(Expr),
+    Sep(LitStr),
+}
+
+impl Arg {
+    fn parse_option(input: ParseStream) -> syn::Result<Arg> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        match name.to_string().as_str() {
+            "whitespace" => Ok(Arg::Whitespace(input.parse()?)),
+            "with" => Ok(Arg::With(input.parse()?)),
+            "sep" => Ok(Arg::Sep(input.parse()?)),
+            _ => Err(syn::Error::new_spanned(
+                name,
+                "expected `whitespace`, `with` or `sep`",
+            )),
+        }
+    }
+}
+
+fn parse_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("parse"))
+}
+
+// -------------------------------------------------------------------------------------------------
+// Patterns
+// -------------------------------------------------------------------------------------------------
+
+enum Item {
+    Token(LitStr),
+    Field(usize, Span),
+}
+
+fn is_option(input: ParseStream) -> bool {
+    input.peek(Ident) && input.peek2(Token![=])
+}
+
+// A pattern is a sequence of string literals and field references
+fn pattern(attrs: &[Attribute], fields: &Fields) -> syn::Result<Option<Vec<Item>>> {
+    let mut result = None;
+
+    for attr in parse_attrs(attrs) {
+        let items = attr.parse_args_with(|input: ParseStream| {
+            if is_option(input) {
+                input.parse::<TokenStream>()?;
+                return Ok(None);
+            }
+
+            let mut items = Vec::new();
+
+            while !input.is_empty() {
+                if input.peek(LitStr) {
+                    items.push(Item::Token(input.parse()?));
+                } else if input.peek(LitInt) {
+                    let index: LitInt = input.parse()?;
+                    let position = index.base10_parse::<usize>()?;
+
+                    if position >= fields.len() || matches!(fields, Fields::Named(_)) {
+                        return Err(syn::Error::new_spanned(index, "unknown field"));
+                    }
+
+                    items.push(Item::Field(position, index.span()));
+                } else {
+                    let name = Ident::parse(input)?;
+                    let position = fields
+                        .iter()
+                        .position(|field| field.ident.as_ref() == Some(&name))
+                        .ok_or_else(|| syn::Error::new_spanned(&name, "unknown field"))?;
+
+                    items.push(Item::Field(position, name.span()));
+                }
+            }
+
+            Ok(Some(items))
+        })?;
+
+        if let Some(items) = items {
+            if result.is_some() {
+                return Err(syn::Error::new_spanned(attr, "duplicate parse pattern"));
+            }
+            result = Some(items);
+        }
+    }
+
+    Ok(result)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Code generation
+// -------------------------------------------------------------------------------------------------
+
+fn build(
+    path: &Path,
+    fields: &Fields,
+    container: &Container,
+    attrs: &[Attribute],
+) -> syn::Result<TokenStream> {
+    let items = match pattern(attrs, fields)? {
+        Some(items) => items,
+        None => (0..fields.len())
+            .map(|position| Item::Field(position, Span::call_site()))
+            .collect(),
+    };
+
+    let mut used = vec![false; fields.len()];
+    let mut parser: Option<TokenStream> = None;
+    let mut binding: Option<TokenStream> = None;
+
+    for item in &items {
+        let (element, bound) = match item {
+            Item::Token(lit) => (quote!(#lit), quote!(_)),
+            Item::Field(position, span) => {
+                if used[*position] {
+                    return Err(syn::Error::new(*span, "field used twice in parse pattern"));
+                }
+                used[*position] = true;
+
+                let field = fields.iter().nth(*position).unwrap();
+                let options = Field::from(&field.attrs)?;
+                let ty = &field.ty;
+                let var = format_ident!("__field{}", position);
+                let element = match (options.with, options.sep) {
+                    (Some(expr), _) => quote!(#expr),
+                    (None, Some(sep)) => {
+                        let ws = container
+                            .whitespace
+                            .as_ref()
+                            .map_or(String::new(), LitStr::value);
+                        quote!(::parsecute::parsers::derive::separated(#sep, #ws))
+                    }
+                    (None, None) => quote!(::parsecute::parsers::derive::parsed::<#ty>()),
+                };
+
+                (element, quote!(#var))
+            }
+        };
+
+        let element = match &container.whitespace {
+            Some(ws) => quote!(skip(String::from(#ws)).then_right(#element)),
+            None => element,
+        };
+
+        parser = Some(match parser {
+            None => element,
+            Some(parser) => quote!(#parser.then(#element)),
+        });
+        binding = Some(match binding {
+            None => bound,
+            Some(binding) => quote!((#binding, #bound)),
+        });
+    }
+
+    if let Some(position) = used.iter().position(|used| !used) {
+        let field = fields.iter().nth(position).unwrap();
+
+        return Err(syn::Error::new_spanned(
+            field,
+            "field missing in parse pattern",
+        ));
+    }
+
+    let vars = (0..fields.len()).map(|position| format_ident!("__field{}", position));
+    let value = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #vars),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#vars),*)),
+        Fields::Unit => quote!(#path),
+    };
+
+    match (parser, binding) {
+        (Some(parser), Some(binding)) => Ok(quote!(#parser.fmap(|#binding| #value))),
+        _ => Err(syn::Error::new_spanned(
+            path,
+            "an empty parse pattern is not allowed",
+        )),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -1,8 +1,12 @@
 use core::marker::PhantomData;
 
 use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+use crate::parsers::limits::*;
+use crate::parsers::lint::*;
 use crate::parsers::parser::*;
 use crate::parsers::response::*;
+use crate::parsers::rule::*;
 
 // -------------------------------------------------------------------------------------------------
 // Parser type definition
@@ -83,30 +87,34 @@ where
 
 // -------------------------------------------------------------------------------------------------
 
-pub struct Satisfy<E, A>(pub E, pub Box<Fn(&A) -> bool>)
+pub type SatisfyFn<'b, A> = Box<dyn Fn(&A) -> bool + 'b>;
+
+pub struct Satisfy<E, A, F = SatisfyFn<'static, A>>(pub E, pub F, pub PhantomData<A>)
 where
     E: Parser<A>;
 
-impl<E, A> Parser<A> for Satisfy<E, A> where E: Parser<A> {}
+impl<E, A, F> Parser<A> for Satisfy<E, A, F> where E: Parser<A> {}
 
 #[inline]
-pub fn satisfy<E, A, F: 'static>(p: E, f: F) -> Satisfy<E, A>
+pub fn satisfy<E, A, F>(p: E, f: F) -> Satisfy<E, A, F>
 where
     E: Parser<A>,
     F: Fn(&A) -> bool,
 {
-    Satisfy(p, Box::new(f))
+    Satisfy(p, f, PhantomData)
 }
 
 pub trait SatisfyOperation<E, A>
 where
     E: Parser<A>,
 {
-    fn satisfy<F: 'static>(self, f: F) -> Satisfy<E, A>
+    fn satisfy_box<'b>(self, f: SatisfyFn<'b, A>) -> Satisfy<E, A, SatisfyFn<'b, A>>;
+
+    fn satisfy<F>(self, f: F) -> Satisfy<E, A, F>
     where
         F: Fn(&A) -> bool;
 
-    fn filter<F: 'static>(self, f: F) -> Satisfy<E, A>
+    fn filter<F>(self, f: F) -> Satisfy<E, A, F>
     where
         F: Fn(&A) -> bool;
 }
@@ -116,14 +124,18 @@ where
     E: Parser<A>,
 {
     #[inline]
-    fn satisfy<F: 'static>(self, f: F) -> Satisfy<E, A>
+    fn satisfy_box<'b>(self, f: SatisfyFn<'b, A>) -> Satisfy<E, A, SatisfyFn<'b, A>> {
+        satisfy(self, f)
+    }
+    #[inline]
+    fn satisfy<F>(self, f: F) -> Satisfy<E, A, F>
     where
         F: Fn(&A) -> bool,
     {
         satisfy(self, f)
     }
     #[inline]
-    fn filter<F: 'static>(self, f: F) -> Satisfy<E, A>
+    fn filter<F>(self, f: F) -> Satisfy<E, A, F>
     where
         F: Fn(&A) -> bool,
     {
@@ -133,19 +145,19 @@ where
 
 // -------------------------------------------------------------------------------------------------
 
-pub struct Lazy<E, A>(pub Box<Fn() -> E>, pub PhantomData<A>)
+pub struct Lazy<E, A, F = Box<dyn Fn() -> E>>(pub F, pub PhantomData<(E, A)>)
 where
     E: Parser<A>;
 
-impl<E, A> Parser<A> for Lazy<E, A> where E: Parser<A> {}
+impl<E, A, F> Parser<A> for Lazy<E, A, F> where E: Parser<A> {}
 
 #[inline]
-pub fn lazy<E, A, F: 'static>(p: F) -> Lazy<E, A>
+pub fn lazy<E, A, F>(p: F) -> Lazy<E, A, F>
 where
     E: Parser<A>,
     F: Fn() -> E,
 {
-    Lazy(Box::new(p), PhantomData)
+    Lazy(p, PhantomData)
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -162,6 +174,14 @@ where
 
         response(Some(v.clone()), o, false)
     }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Empty
+    }
 }
 
 impl<'a, A> Parsable<'a, A> for Return<A> {
@@ -178,6 +198,10 @@ impl<'a, A> Executable<'a, A> for Fail {
     fn execute(&self, _: &'a [u8], o: usize) -> Response<A> {
         response(None, o, false)
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Fail
+    }
 }
 
 impl<'a, A> Parsable<'a, A> for Fail {
@@ -198,6 +222,10 @@ impl<'a> Executable<'a, u8> for Any {
 
         response(None, o, false)
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Any
+    }
 }
 
 impl<'a> Parsable<'a, u8> for Any {
@@ -218,6 +246,14 @@ impl<'a> Executable<'a, ()> for Eos {
     fn execute(&self, s: &'a [u8], o: usize) -> Response<()> {
         self.parse_only(s, o)
     }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Eos
+    }
 }
 
 impl<'a> Parsable<'a, ()> for Eos {
@@ -246,6 +282,18 @@ where
             _ => r,
         }
     }
+
+    fn nullable(&self) -> bool {
+        self.0.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        self.0.describe()
+    }
 }
 
 impl<'a, A, E> Parsable<'a, A> for Try<E, A>
@@ -278,16 +326,29 @@ where
             _ => response(None, result.o, result.c),
         }
     }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Lookahead(Box::new(self.0.describe()))
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
 
-impl<'a, A, E> Executable<'a, A> for Satisfy<E, A>
+impl<'a, A, E, F> Executable<'a, A> for Satisfy<E, A, F>
 where
     E: Executable<'a, A> + Parser<A>,
+    F: Fn(&A) -> bool,
 {
     fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
-        let Satisfy(p, c) = self;
+        let Satisfy(p, c, _) = self;
         let result = p.execute(s, o);
 
         match result.v {
@@ -301,14 +362,27 @@ where
             _ => result,
         }
     }
+
+    fn nullable(&self) -> bool {
+        self.0.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Filter(Box::new(self.0.describe()))
+    }
 }
 
-impl<'a, A, E> Parsable<'a, A> for Satisfy<E, A>
+impl<'a, A, E, F> Parsable<'a, A> for Satisfy<E, A, F>
 where
     E: Executable<'a, A> + Parser<A>,
+    F: Fn(&A) -> bool,
 {
     fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
-        let Satisfy(p, c) = self;
+        let Satisfy(p, c, _) = self;
         let result = p.execute(s, o);
 
         match result.v {
@@ -326,27 +400,153 @@ where
 
 // -------------------------------------------------------------------------------------------------
 
-impl<'a, A, E> Executable<'a, A> for Lazy<E, A>
+impl<'a, A, E, F> Executable<'a, A> for Lazy<E, A, F>
 where
     E: Executable<'a, A> + Parser<A>,
+    F: Fn() -> E,
 {
     #[inline]
     fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
         let Lazy(parser, _) = self;
 
-        parser().execute(s, o)
+        guarded(o, || parser().execute(s, o))
+    }
+
+    fn nullable(&self) -> bool {
+        let Lazy(parser, _) = self;
+
+        analysing(
+            format!("lazy {}", std::any::type_name::<F>()),
+            false,
+            || parser().nullable(),
+        )
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        let Lazy(parser, _) = self;
+
+        if walking() {
+            return;
+        }
+
+        analysing(format!("lazy {}", std::any::type_name::<F>()), (), || {
+            parser().check(warnings)
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        let Lazy(parser, _) = self;
+
+        expanding(format!("lazy {}", std::any::type_name::<F>()), || {
+            parser().describe()
+        })
     }
 }
 
-impl<'a, A, E> Parsable<'a, A> for Lazy<E, A>
+impl<'a, A, E, F> Parsable<'a, A> for Lazy<E, A, F>
 where
     E: Parsable<'a, A> + Parser<A>,
+    F: Fn() -> E,
 {
     #[inline]
     fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
         let Lazy(parser, _) = self;
 
-        parser().parse_only(s, o)
+        guarded(o, || parser().parse_only(s, o))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Stateful parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<A> Stateless for Return<A> {}
+
+impl Stateless for Fail {}
+
+impl Stateless for Any {}
+
+impl Stateless for Eos {}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, A, E, S> StatefulExecutable<'a, A, S> for Try<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    S: Clone,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Try(p, _) = self;
+        let saved = state.clone();
+        let r = p.execute_with(s, o, state);
+
+        match r.v {
+            None => {
+                *state = saved;
+                response(None, o, false)
+            }
+            _ => r,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, A, E, S> StatefulExecutable<'a, A, S> for Lookahead<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    S: Clone,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Lookahead(p, _) = self;
+        let saved = state.clone();
+        let result = p.execute_with(s, o, state);
+
+        *state = saved;
+
+        match result.v {
+            Some(value) => response(Some(value), o, result.c),
+            _ => response(None, result.o, result.c),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, A, E, F, S> StatefulExecutable<'a, A, S> for Satisfy<E, A, F>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    F: Fn(&A) -> bool,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Satisfy(p, c, _) = self;
+        let result = p.execute_with(s, o, state);
+
+        match result.v {
+            Some(value) => {
+                if (c)(&value) {
+                    response(Some(value), result.o, result.c)
+                } else {
+                    response(None, result.o, result.c)
+                }
+            }
+            _ => result,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, A, E, F, S> StatefulExecutable<'a, A, S> for Lazy<E, A, F>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    F: Fn() -> E,
+{
+    #[inline]
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Lazy(parser, _) = self;
+
+        guarded(o, || parser().execute_with(s, o, state))
     }
 }
 
@@ -0,0 +1,167 @@
+use std::convert::TryInto;
+
+// -------------------------------------------------------------------------------------------------
+// Byte set definition
+// -------------------------------------------------------------------------------------------------
+
+// A byte set is a 256 bits lookup table. Sets of at most four bytes like whitespaces or
+// string delimiters are also scanned eight bytes at a time.
+
+const SMALL: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteSet {
+    table: [u64; 4],
+    bytes: [u8; SMALL],
+    len: usize,
+}
+
+impl ByteSet {
+    pub const fn empty() -> ByteSet {
+        ByteSet {
+            table: [0; 4],
+            bytes: [0; SMALL],
+            len: 0,
+        }
+    }
+
+    // Constant in order to build fixed sets like delimiters once
+    pub const fn from_bytes(bytes: &[u8]) -> ByteSet {
+        let mut set = ByteSet::empty();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let b = bytes[i];
+
+            if !set.contains(b) {
+                set.table[(b >> 6) as usize] |= 1 << (b & 63);
+
+                if set.len < SMALL {
+                    set.bytes[set.len] = b;
+                }
+
+                set.len += 1;
+            }
+
+            i += 1;
+        }
+
+        set
+    }
+
+    pub fn from_fn<F>(f: F) -> ByteSet
+    where
+        F: Fn(&u8) -> bool,
+    {
+        let mut set = ByteSet::empty();
+
+        for b in 0..=255 {
+            if f(&b) {
+                set.insert(b);
+            }
+        }
+
+        set
+    }
+
+    pub fn insert(&mut self, b: u8) {
+        if self.contains(b) {
+            return;
+        }
+
+        self.table[(b >> 6) as usize] |= 1 << (b & 63);
+
+        if self.len < SMALL {
+            self.bytes[self.len] = b;
+        }
+
+        self.len += 1;
+    }
+
+    #[inline]
+    pub const fn contains(&self, b: u8) -> bool {
+        self.table[(b >> 6) as usize] & (1 << (b & 63)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn union(&self, other: &ByteSet) -> ByteSet {
+        ByteSet::from_fn(|b| self.contains(*b) || other.contains(*b))
+    }
+
+    pub fn complement(&self) -> ByteSet {
+        ByteSet::from_fn(|b| !self.contains(*b))
+    }
+
+    // Offset of the first byte from o which is not in the set
+    pub fn skip(&self, s: &[u8], o: usize) -> usize {
+        let mut n = o;
+
+        if self.len <= SMALL {
+            while n + 8 <= s.len() && self.matches(word(s, n)) == HIGH {
+                n += 8;
+            }
+        }
+
+        while n < s.len() && self.contains(s[n]) {
+            n += 1;
+        }
+
+        n
+    }
+
+    // Offset of the first byte from o which is in the set
+    pub fn find(&self, s: &[u8], o: usize) -> Option<usize> {
+        let mut n = o;
+
+        if self.len <= SMALL {
+            while n + 8 <= s.len() && self.matches(word(s, n)) == 0 {
+                n += 8;
+            }
+        }
+
+        while n < s.len() {
+            if self.contains(s[n]) {
+                return Some(n);
+            }
+            n += 1;
+        }
+
+        None
+    }
+
+    // High bit set for each byte of the word belonging to a small set
+    #[inline]
+    fn matches(&self, w: u64) -> u64 {
+        self.bytes[..self.len]
+            .iter()
+            .fold(0, |m, b| m | zeros(w ^ (LOW * *b as u64)))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Word at a time helpers
+// -------------------------------------------------------------------------------------------------
+
+const LOW: u64 = 0x0101_0101_0101_0101;
+const HIGH: u64 = 0x8080_8080_8080_8080;
+const MASK: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+
+#[inline]
+fn word(s: &[u8], o: usize) -> u64 {
+    u64::from_le_bytes(s[o..o + 8].try_into().unwrap())
+}
+
+// High bit set for each null byte of the word without carry propagation
+#[inline]
+fn zeros(w: u64) -> u64 {
+    !(((w & MASK) + MASK) | w | MASK)
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,272 @@
+use crate::parsers::bytes::*;
+use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+pub struct ByteClass(pub ByteSet);
+
+impl Parser<u8> for ByteClass {}
+
+// -------------------------------------------------------------------------------------------------
+
+// ASCII characters are checked with a lookup table and other ones with sorted ranges
+
+pub struct CharClass {
+    ascii: ByteSet,
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl Parser<char> for CharClass {}
+
+impl CharClass {
+    fn new(items: &[(char, char)], negated: bool) -> CharClass {
+        let mut ascii = ByteSet::empty();
+        let mut ranges = Vec::new();
+
+        for (start, end) in items {
+            for c in *start..=(*end).min('\x7f') {
+                ascii.insert(c as u8);
+            }
+            if *end > '\x7f' {
+                ranges.push(((*start).max('\u{80}'), *end));
+            }
+        }
+
+        ranges.sort_unstable();
+        ranges.dedup_by(|next, previous| {
+            let merged = next.0 as u32 <= previous.1 as u32 + 1;
+            if merged {
+                previous.1 = previous.1.max(next.1);
+            }
+            merged
+        });
+
+        CharClass {
+            ascii,
+            ranges,
+            negated,
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, c: char) -> bool {
+        let found = if c.is_ascii() {
+            self.ascii.contains(c as u8)
+        } else {
+            let n = self.ranges.partition_point(|(start, _)| *start <= c);
+            n > 0 && c <= self.ranges[n - 1].1
+        };
+
+        found != self.negated
+    }
+
+    // Specification using the `char_class` syntax
+    pub fn spec(&self) -> String {
+        let mut ranges: Vec<(char, char)> = Vec::new();
+
+        for b in 0..=0x7f_u8 {
+            if self.ascii.contains(b) {
+                match ranges.last_mut() {
+                    Some((_, end)) if *end as u32 + 1 == b as u32 => *end = b as char,
+                    _ => ranges.push((b as char, b as char)),
+                }
+            }
+        }
+
+        ranges.extend(self.ranges.iter().cloned());
+
+        if self.negated {
+            format!("^{}", class_ranges(&ranges))
+        } else {
+            class_ranges(&ranges)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub trait ClassSource {
+    type Class;
+
+    fn class(self, negated: bool) -> Self::Class;
+}
+
+impl ClassSource for &[u8] {
+    type Class = ByteClass;
+
+    fn class(self, negated: bool) -> ByteClass {
+        let set = ByteSet::from_bytes(self);
+
+        ByteClass(if negated { set.complement() } else { set })
+    }
+}
+
+impl<const N: usize> ClassSource for &[u8; N] {
+    type Class = ByteClass;
+
+    fn class(self, negated: bool) -> ByteClass {
+        self[..].class(negated)
+    }
+}
+
+impl ClassSource for &str {
+    type Class = CharClass;
+
+    fn class(self, negated: bool) -> CharClass {
+        let items: Vec<(char, char)> = self.chars().map(|c| (c, c)).collect();
+
+        CharClass::new(&items, negated)
+    }
+}
+
+#[inline]
+pub fn one_of<T>(items: T) -> T::Class
+where
+    T: ClassSource,
+{
+    items.class(false)
+}
+
+#[inline]
+pub fn none_of<T>(items: T) -> T::Class
+where
+    T: ClassSource,
+{
+    items.class(true)
+}
+
+// Character class syntax: an optional leading `^` for negation followed by characters
+// and `a-z` ranges. A backslash escapes the next character and a trailing `-` is a character.
+// An invalid class, like a reversed range or a trailing backslash, is rejected by
+// `try_char_class` while `char_class` panics.
+
+pub fn char_class(spec: &str) -> CharClass {
+    try_char_class(spec).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn try_char_class(spec: &str) -> Result<CharClass, String> {
+    let (negated, items) = class_items(spec)?;
+
+    Ok(CharClass::new(&items, negated))
+}
+
+pub(crate) fn class_items(spec: &str) -> Result<(bool, Vec<(char, char)>), String> {
+    let mut chars = spec.chars().peekable();
+    let negated = chars.peek() == Some(&'^');
+    let mut items = Vec::new();
+
+    if negated {
+        chars.next();
+    }
+
+    let next = |chars: &mut std::iter::Peekable<std::str::Chars>| match chars.next() {
+        Some('\\') => chars
+            .next()
+            .ok_or_else(|| "invalid character class: trailing escape".to_string()),
+        Some(c) => Ok(c),
+        None => unreachable!(),
+    };
+
+    while chars.peek().is_some() {
+        let start = next(&mut chars)?;
+        let mut range = chars.clone();
+
+        if range.next() == Some('-') && range.peek().is_some() {
+            chars.next();
+            let end = next(&mut chars)?;
+
+            if start > end {
+                return Err(format!("invalid character class: {}-{}", start, end));
+            }
+            items.push((start, end));
+        } else {
+            items.push((start, start));
+        }
+    }
+
+    Ok((negated, items))
+}
+
+// -------------------------------------------------------------------------------------------------
+// UTF-8 decoding
+// -------------------------------------------------------------------------------------------------
+
+#[inline]
+fn decode(s: &[u8], o: usize) -> Option<(char, usize)> {
+    let len = match s.get(o)? {
+        b if *b < 0x80 => return Some((*b as char, 1)),
+        b if *b >= 0xf0 => 4,
+        b if *b >= 0xe0 => 3,
+        _ => 2,
+    };
+
+    let c = std::str::from_utf8(s.get(o..o + len)?)
+        .ok()?
+        .chars()
+        .next()?;
+
+    Some((c, len))
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a> Executable<'a, u8> for ByteClass {
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<u8> {
+        let ByteClass(bytes) = self;
+
+        if o < s.len() && bytes.contains(s[o]) {
+            return response(Some(s[o]), o + 1, true);
+        }
+
+        response(None, o, false)
+    }
+
+    fn describe(&self) -> Grammar {
+        byte_class(&self.0)
+    }
+}
+
+impl<'a> Parsable<'a, u8> for ByteClass {
+    #[inline]
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+impl Stateless for ByteClass {}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a> Executable<'a, char> for CharClass {
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<char> {
+        match decode(s, o) {
+            Some((c, len)) if self.contains(c) => response(Some(c), o + len, true),
+            _ => response(None, o, false),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::CharClass(self.spec())
+    }
+}
+
+impl<'a> Parsable<'a, char> for CharClass {
+    #[inline]
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+impl Stateless for CharClass {}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,172 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+
+use crate::parsers::execution::*;
+use crate::parsers::label::*;
+use crate::parsers::limits::*;
+use crate::parsers::lint::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Execution context definition
+// -------------------------------------------------------------------------------------------------
+
+// Memoization, labelled failures and limits are only enabled within a context while the non
+// consuming repetition policy can be changed for its execution. A rejected non consuming
+// repetition is reported like a reached limit.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Context {
+    pub memo: bool,
+    pub labels: bool,
+    pub limits: Option<Limits>,
+    pub non_consuming: NonConsuming,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Context {
+            memo: false,
+            labels: false,
+            limits: None,
+            non_consuming: NonConsuming::Reject,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExecutionError {
+    Limit(LimitError),
+    // Repetition of a parser succeeding without consuming input at the given offset
+    NonConsuming(usize),
+    // Furthest labelled failure, only reported when the parse fails
+    Failure(Failure),
+}
+
+// -------------------------------------------------------------------------------------------------
+// Execution state
+// -------------------------------------------------------------------------------------------------
+
+// Each memoization scope has its own identifier and the failure is only recorded when labels are
+// enabled. Limits are checked by guarded executions only, so a context without limits costs
+// nothing to the other parsers.
+
+pub(crate) struct State {
+    pub(crate) memo: Cell<Option<usize>>,
+    pub(crate) scopes: Cell<usize>,
+    pub(crate) budget: Cell<Option<Budget>>,
+    pub(crate) failure: RefCell<Option<Option<Failure>>>,
+    pub(crate) non_consuming: Cell<NonConsuming>,
+    pub(crate) rejected: Cell<Option<usize>>,
+}
+
+thread_local! {
+    static STATE: State = const {
+        State {
+            memo: Cell::new(None),
+            scopes: Cell::new(0),
+            budget: Cell::new(None),
+            failure: RefCell::new(None),
+            non_consuming: Cell::new(NonConsuming::Reject),
+            rejected: Cell::new(None),
+        }
+    };
+}
+
+#[inline]
+pub(crate) fn state<R, F>(f: F) -> R
+where
+    F: FnOnce(&State) -> R,
+{
+    STATE.with(f)
+}
+
+// The enclosing context is restored even if the parser panics
+
+struct Scope {
+    memo: Option<usize>,
+    budget: Option<Budget>,
+    failure: Option<Option<Failure>>,
+    non_consuming: NonConsuming,
+    rejected: Option<usize>,
+}
+
+impl Scope {
+    fn enter(context: Context) -> Self {
+        STATE.with(|state| {
+            let memo = if context.memo {
+                let id = state.scopes.get().wrapping_add(1);
+                state.scopes.set(id);
+                Some(id)
+            } else {
+                None
+            };
+
+            Scope {
+                memo: state.memo.replace(memo),
+                budget: state.budget.replace(context.limits.map(Budget::new)),
+                failure: state
+                    .failure
+                    .replace(if context.labels { Some(None) } else { None }),
+                non_consuming: state.non_consuming.replace(context.non_consuming),
+                rejected: state.rejected.replace(None),
+            }
+        })
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        STATE.with(|state| {
+            state.memo.set(self.memo);
+            state.budget.set(self.budget);
+            state.failure.replace(self.failure.take());
+            state.non_consuming.set(self.non_consuming);
+            state.rejected.set(self.rejected);
+        })
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Execution within a context
+// -------------------------------------------------------------------------------------------------
+
+pub fn within<R, F>(context: Context, f: F) -> (R, Option<ExecutionError>)
+where
+    F: FnOnce() -> R,
+{
+    let scope = Scope::enter(context);
+    let result = f();
+    let error = STATE.with(|state| {
+        let limit = state.budget.get().and_then(|b| b.error);
+        let rejected = state.rejected.get();
+        let failure = state.failure.borrow().clone().flatten();
+
+        limit
+            .map(ExecutionError::Limit)
+            .or(rejected.map(ExecutionError::NonConsuming))
+            .or(failure.map(ExecutionError::Failure))
+    });
+
+    drop(scope);
+
+    (result, error)
+}
+
+pub fn execute_within<'a, E, A>(
+    p: &E,
+    s: &'a [u8],
+    o: usize,
+    context: Context,
+) -> Result<Response<A>, ExecutionError>
+where
+    E: Executable<'a, A>,
+{
+    match within(context, || p.execute(s, o)) {
+        (response, Some(ExecutionError::Failure(_))) if response.v.is_some() => Ok(response),
+        (_, Some(error)) => Err(error),
+        (response, None) => Ok(response),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
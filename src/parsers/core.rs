@@ -1,4 +1,7 @@
 use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+use crate::parsers::limits::*;
+use crate::parsers::lint::*;
 use crate::parsers::parser::*;
 use crate::parsers::response::*;
 
@@ -22,6 +25,40 @@ impl<'a, A> Executable<'a, A> for Parsec<'a, A> {
     fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
         let Parsec(e) = self;
 
-        e.execute(s, o)
+        guarded(o, || e.execute(s, o))
+    }
+
+    fn nullable(&self) -> bool {
+        self.0.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        self.0.describe()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Stateful Parser used for type simplification
+// -------------------------------------------------------------------------------------------------
+
+pub struct StatefulParsec<'a, A, S>(pub Box<dyn StatefulExecutable<'a, A, S> + 'a>);
+
+impl<'a, A, S> Parser<A> for StatefulParsec<'a, A, S> {}
+
+pub fn stateful_parsec<'a, A, S>(
+    p: Box<dyn StatefulExecutable<'a, A, S> + 'a>,
+) -> StatefulParsec<'a, A, S> {
+    StatefulParsec::<'a>(p)
+}
+
+impl<'a, A, S> StatefulExecutable<'a, A, S> for StatefulParsec<'a, A, S> {
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let StatefulParsec(e) = self;
+
+        guarded(o, || e.execute_with(s, o, state))
     }
 }
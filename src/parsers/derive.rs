@@ -0,0 +1,155 @@
+use std::cell::Cell;
+
+use crate::parsers::core::*;
+use crate::parsers::execution::*;
+use crate::parsers::flow::*;
+use crate::parsers::grammar::*;
+use crate::parsers::lint::*;
+use crate::parsers::monadic::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+use crate::parsers::rule::*;
+
+pub use parsecute_derive::Parse;
+
+// -------------------------------------------------------------------------------------------------
+// Parsable types
+// -------------------------------------------------------------------------------------------------
+
+// A type implementing `Parse` gives its own parser. Such implementation is usually derived
+// from the type definition with `#[derive(Parse)]`.
+
+pub trait Parse<'a>: Sized + 'a {
+    fn parser() -> Parsec<'a, Self>;
+}
+
+impl<'a, T> Parse<'a> for Box<T>
+where
+    T: Parse<'a>,
+{
+    fn parser() -> Parsec<'a, Self> {
+        Parsec(Box::new(parsed::<T>().fmap(Box::new)))
+    }
+}
+
+impl<'a, T> Parse<'a> for Option<T>
+where
+    T: Parse<'a>,
+{
+    fn parser() -> Parsec<'a, Self> {
+        Parsec(Box::new(parsed::<T>().opt()))
+    }
+}
+
+impl<'a, T> Parse<'a> for Vec<T>
+where
+    T: Parse<'a>,
+{
+    fn parser() -> Parsec<'a, Self> {
+        Parsec(Box::new(parsed::<T>().optrep()))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// The parser is built on its first use in order to accept recursive types and is then kept in a
+// rule. A recursive type therefore builds one parser for each nesting level reached at most.
+
+pub struct Parsed<'a, T>(Rule<'a, T>, Cell<bool>);
+
+impl<'a, T> Parser<T> for Parsed<'a, T> {}
+
+#[inline]
+pub fn parsed<'a, T>() -> Parsed<'a, T>
+where
+    T: Parse<'a>,
+{
+    Parsed(rule(), Cell::new(false))
+}
+
+impl<'a, T> Parsed<'a, T>
+where
+    T: Parse<'a>,
+{
+    fn built(&self) -> &Rule<'a, T> {
+        let Parsed(rule, built) = self;
+
+        if !built.replace(true) {
+            rule.define(T::parser());
+        }
+
+        rule
+    }
+}
+
+impl<'a, T> Executable<'a, T> for Parsed<'a, T>
+where
+    T: Parse<'a>,
+{
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<T> {
+        self.built().execute(s, o)
+    }
+
+    fn nullable(&self) -> bool {
+        analysing(
+            format!("parsed {}", std::any::type_name::<T>()),
+            false,
+            || self.built().nullable(),
+        )
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        if walking() {
+            return;
+        }
+
+        analysing(format!("parsed {}", std::any::type_name::<T>()), (), || {
+            self.built().check(warnings)
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        expanding(format!("parsed {}", std::any::type_name::<T>()), || {
+            self.built().describe()
+        })
+    }
+}
+
+impl<'a, T> Parsable<'a, T> for Parsed<'a, T>
+where
+    T: Parse<'a>,
+{
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+// Whitespace characters are skipped around each separator
+
+pub fn separated<'a, T>(sep: &str, whitespace: &str) -> Parsec<'a, Vec<T>>
+where
+    T: Parse<'a>,
+{
+    let item = || skip(whitespace.to_string()).then_right(parsed::<T>());
+
+    Parsec(Box::new(
+        parsed::<T>()
+            .then(
+                skip(whitespace.to_string())
+                    .then_right(sep.to_string())
+                    .then_right(item())
+                    .optrep(),
+            )
+            .opt()
+            .fmap(|v| match v {
+                Some((e, mut v)) => {
+                    v.insert(0, e);
+                    v
+                }
+                None => Vec::new(),
+            }),
+    ))
+}
+
+// -------------------------------------------------------------------------------------------------
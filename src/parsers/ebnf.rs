@@ -0,0 +1,254 @@
+use crate::parsers::charset::*;
+use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+
+// -------------------------------------------------------------------------------------------------
+// EBNF notations
+// -------------------------------------------------------------------------------------------------
+
+// ISO/IEC 14977 EBNF uses `=`, `,` and brackets for options and repetitions while the W3C
+// notation used by the XML specification uses `::=`, juxtaposition and postfix operators.
+// Parsers without an EBNF equivalent are rendered as special sequences or comments.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    Iso,
+    W3c,
+}
+
+pub fn ebnf<'a, E, A>(p: &E, notation: Notation) -> String
+where
+    E: Executable<'a, A>,
+{
+    to_ebnf(&p.describe(), notation)
+}
+
+// One definition per line, the unnamed root rule being called `grammar`
+
+pub fn to_ebnf(grammar: &Grammar, notation: Notation) -> String {
+    grammar
+        .rules("grammar")
+        .iter()
+        .map(|(name, g)| match notation {
+            Notation::Iso => format!("{} = {} ;\n", name, render(g, notation, ALT)),
+            Notation::W3c => format!("{} ::= {}\n", name, render(g, notation, ALT)),
+        })
+        .collect()
+}
+
+// -------------------------------------------------------------------------------------------------
+// Rendering
+// -------------------------------------------------------------------------------------------------
+
+const ALT: u8 = 0;
+const SEQ: u8 = 1;
+const POSTFIX: u8 = 2;
+
+fn parenthesized(s: String, precedence: u8, required: u8) -> String {
+    if precedence > required {
+        format!("({})", s)
+    } else {
+        s
+    }
+}
+
+fn special(text: &str, notation: Notation) -> String {
+    match notation {
+        Notation::Iso => format!("? {} ?", text),
+        Notation::W3c => comment(text, notation),
+    }
+}
+
+// A comment delimiter found in the text like a `*/` terminator is split so the comment does not end
+// early
+
+fn comment(text: &str, notation: Notation) -> String {
+    match notation {
+        Notation::Iso => format!("(* {} *)", text.replace("*)", "* )")),
+        Notation::W3c => format!("/* {} */", text.replace("*/", "* /")),
+    }
+}
+
+fn render(grammar: &Grammar, notation: Notation, precedence: u8) -> String {
+    let iso = notation == Notation::Iso;
+
+    match grammar {
+        Grammar::Empty if iso => String::new(),
+        Grammar::Empty => comment("empty", notation),
+        Grammar::Fail => special("fail", notation),
+        Grammar::Any if iso => special("any byte", notation),
+        Grammar::Any => "[#x00-#xFF]".to_string(),
+        Grammar::Eos => special("end of input", notation),
+        Grammar::Literal(s) if s.is_empty() => render(&Grammar::Empty, notation, precedence),
+        Grammar::Literal(s) => {
+            let mut parts = literal(s, notation);
+            let separator = if iso { ", " } else { " " };
+
+            if parts.len() == 1 {
+                parts.remove(0)
+            } else {
+                parenthesized(parts.join(separator), precedence, SEQ)
+            }
+        }
+        Grammar::CharClass(spec) if iso => {
+            let spec: String = spec
+                .chars()
+                .map(|c| match c {
+                    c if c.is_control() => c.escape_default().to_string(),
+                    c => c.to_string(),
+                })
+                .collect();
+
+            special(&format!("[{}]", spec), notation)
+        }
+        Grammar::CharClass(spec) => class(spec),
+        Grammar::ByteClass(ranges) if iso => {
+            special(&format!("byte {}", byte_ranges(ranges)), notation)
+        }
+        Grammar::ByteClass(ranges) => byte_ranges(ranges),
+        Grammar::Seq(items) => {
+            let separator = if iso { ", " } else { " " };
+            let items: Vec<String> = items
+                .iter()
+                .map(|g| render(g, notation, SEQ))
+                .filter(|s| !s.is_empty())
+                .collect();
+
+            parenthesized(items.join(separator), precedence, SEQ)
+        }
+        // An empty alternative makes the other ones optional
+        Grammar::Alt(items) if items.contains(&Grammar::Empty) => {
+            let items = items.iter().filter(|g| **g != Grammar::Empty).cloned();
+
+            render(
+                &Grammar::Opt(Box::new(Grammar::alt(items.collect()))),
+                notation,
+                precedence,
+            )
+        }
+        Grammar::Alt(items) => {
+            let items: Vec<String> = items.iter().map(|g| render(g, notation, SEQ)).collect();
+
+            parenthesized(items.join(" | "), precedence, ALT)
+        }
+        Grammar::Rep(g) if iso => parenthesized(
+            format!(
+                "{}, {{ {} }}",
+                render(g, notation, SEQ),
+                render(g, notation, ALT)
+            ),
+            precedence,
+            SEQ,
+        ),
+        Grammar::Rep(g) => parenthesized(
+            format!("{}+", render(g, notation, POSTFIX)),
+            precedence,
+            SEQ,
+        ),
+        Grammar::OptRep(g) if iso => format!("{{ {} }}", render(g, notation, ALT)),
+        Grammar::OptRep(g) => parenthesized(
+            format!("{}*", render(g, notation, POSTFIX)),
+            precedence,
+            SEQ,
+        ),
+        Grammar::Opt(g) if iso => format!("[ {} ]", render(g, notation, ALT)),
+        Grammar::Opt(g) => parenthesized(
+            format!("{}?", render(g, notation, POSTFIX)),
+            precedence,
+            SEQ,
+        ),
+        // Lookahead does not consume input and is described as a comment only
+        Grammar::Lookahead(g) => comment(
+            &format!("followed by {}", render(g, notation, ALT)),
+            notation,
+        ),
+        Grammar::Until(g) => special(
+            &format!("bytes up to {}", render(g, notation, ALT)),
+            notation,
+        ),
+        Grammar::Filter(g) => parenthesized(
+            format!(
+                "{} {}",
+                render(g, notation, SEQ),
+                comment("filtered", notation)
+            ),
+            precedence,
+            SEQ,
+        ),
+        Grammar::Terminal(name) => special(name, notation),
+        Grammar::Named(name, _) | Grammar::Rule(name) => name.clone(),
+        Grammar::Dynamic => special("dynamic", notation),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// A literal is split into quoted parts and control characters which cannot be quoted
+
+fn literal(s: &str, notation: Notation) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut run = String::new();
+
+    for c in s.chars() {
+        if c.is_control() {
+            parts.extend(quoted(&run));
+            run.clear();
+            parts.push(match notation {
+                Notation::Iso => format!("? U+{:04X} ?", c as u32),
+                Notation::W3c => format!("#x{:02X}", c as u32),
+            });
+        } else {
+            run.push(c);
+        }
+    }
+
+    parts.extend(quoted(&run));
+
+    parts
+}
+
+fn quoted(s: &str) -> Vec<String> {
+    if s.is_empty() {
+        vec![]
+    } else if !s.contains('"') {
+        vec![format!("\"{}\"", s)]
+    } else if !s.contains('\'') {
+        vec![format!("'{}'", s)]
+    } else {
+        let (before, after) = s.split_at(s.find('"').unwrap());
+        let mut parts = quoted(before);
+
+        parts.push("'\"'".to_string());
+        parts.extend(quoted(&after[1..]));
+
+        parts
+    }
+}
+
+// W3C character classes use `#xN` for characters which are not printable or are special
+
+fn class(spec: &str) -> String {
+    let (negated, items) = match class_items(spec) {
+        Ok(class) => class,
+        Err(_) => return special(&format!("[{}]", spec), Notation::W3c),
+    };
+    let char = |c: char| match c {
+        '[' | ']' | '-' | '^' | '\\' | '#' => format!("#x{:02X}", c as u32),
+        c if c.is_ascii_graphic() || !c.is_ascii() && !c.is_control() => c.to_string(),
+        c => format!("#x{:02X}", c as u32),
+    };
+    let ranges: String = items
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                char(*start)
+            } else {
+                format!("{}-{}", char(*start), char(*end))
+            }
+        })
+        .collect();
+
+    format!("[{}{}]", if negated { "^" } else { "" }, ranges)
+}
+
+// -------------------------------------------------------------------------------------------------
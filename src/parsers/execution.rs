@@ -1,3 +1,5 @@
+use crate::parsers::grammar::Grammar;
+use crate::parsers::lint::Warning;
 use crate::parsers::parser::Parser;
 use crate::parsers::response::Response;
 
@@ -10,6 +12,21 @@ where
     Self: Parser<A>,
 {
     fn execute(&self, s: &'a [u8], o: usize) -> Response<A>;
+
+    // Static analysis: a parser is nullable when it may succeed without consuming input.
+    // Parsers are considered non nullable and without warnings by default.
+
+    fn nullable(&self) -> bool {
+        false
+    }
+
+    fn check(&self, _: &mut Vec<Warning>) {}
+
+    // Grammar description: parsers which cannot be described are considered dynamic.
+
+    fn describe(&self) -> Grammar {
+        Grammar::Dynamic
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -22,3 +39,28 @@ where
 {
     fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()>;
 }
+
+// -------------------------------------------------------------------------------------------------
+// Stateful executable type definition
+// -------------------------------------------------------------------------------------------------
+
+pub trait StatefulExecutable<'a, A, S>
+where
+    Self: Parser<A>,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A>;
+}
+
+// Parsers not depending on the state are executed as is
+
+pub trait Stateless {}
+
+impl<'a, A, S, E> StatefulExecutable<'a, A, S> for E
+where
+    E: Executable<'a, A> + Stateless + Parser<A>,
+{
+    #[inline]
+    fn execute_with(&self, s: &'a [u8], o: usize, _: &mut S) -> Response<A> {
+        self.execute(s, o)
+    }
+}
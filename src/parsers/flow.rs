@@ -1,7 +1,10 @@
 use core::marker::PhantomData;
 
-use crate::parsers::basic::*;
+use crate::parsers::bytes::*;
+use crate::parsers::core::*;
 use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+use crate::parsers::lint::*;
 use crate::parsers::monadic::*;
 use crate::parsers::parser::*;
 use crate::parsers::response::*;
@@ -55,14 +58,18 @@ where
 {
 }
 
+pub type ThenLeft<E, A, R, B> = FMap<And<E, A, R, B>, (A, B), A, fn((A, B)) -> A>;
+
+pub type ThenRight<E, A, R, B> = FMap<And<E, A, R, B>, (A, B), B, fn((A, B)) -> B>;
+
 pub trait AndOperation<E, A, R, B>
 where
     E: Parser<A>,
     R: Parser<B>,
 {
     fn then(self, b: R) -> And<E, A, R, B>;
-    fn then_left(self, b: R) -> FMap<And<E, A, R, B>, (A, B), A>;
-    fn then_right(self, b: R) -> FMap<And<E, A, R, B>, (A, B), B>;
+    fn then_left(self, b: R) -> ThenLeft<E, A, R, B>;
+    fn then_right(self, b: R) -> ThenRight<E, A, R, B>;
 }
 
 impl<E, A, R, B> AndOperation<E, A, R, B> for E
@@ -75,17 +82,79 @@ where
         And(self, b, PhantomData, PhantomData)
     }
     #[inline]
-    fn then_left(self, b: R) -> FMap<And<E, A, R, B>, (A, B), A> {
-        And(self, b, PhantomData, PhantomData).fmap(|(a, _)| a)
+    fn then_left(self, b: R) -> ThenLeft<E, A, R, B> {
+        let left: fn((A, B)) -> A = |(a, _)| a;
+
+        And(self, b, PhantomData, PhantomData).fmap(left)
     }
     #[inline]
-    fn then_right(self, b: R) -> FMap<And<E, A, R, B>, (A, B), B> {
-        And(self, b, PhantomData, PhantomData).fmap(|(_, b)| b)
+    fn then_right(self, b: R) -> ThenRight<E, A, R, B> {
+        let right: fn((A, B)) -> B = |(_, b)| b;
+
+        And(self, b, PhantomData, PhantomData).fmap(right)
     }
 }
 
 // -------------------------------------------------------------------------------------------------
 
+pub struct Choice<T, A>(pub T, PhantomData<A>);
+
+#[inline]
+pub fn choice<T, A>(p: T) -> Choice<T, A>
+where
+    Choice<T, A>: Parser<A>,
+{
+    Choice(p, PhantomData)
+}
+
+pub struct ChoiceVec<'a, A>(pub Vec<Parsec<'a, A>>);
+
+impl<'a, A> Parser<A> for ChoiceVec<'a, A> {}
+
+#[inline]
+pub fn choice_vec<A>(p: Vec<Parsec<A>>) -> ChoiceVec<A> {
+    ChoiceVec(p)
+}
+
+// The table gives the index of the parser selected by each byte, zero being the default one
+
+pub struct Dispatch<'a, A>(pub Box<[usize; 256]>, pub Vec<Parsec<'a, A>>);
+
+impl<'a, A> Parser<A> for Dispatch<'a, A> {}
+
+pub fn dispatch<'a, A>(
+    cases: Vec<(&[u8], Parsec<'a, A>)>,
+    default: Parsec<'a, A>,
+) -> Dispatch<'a, A> {
+    let mut table = Box::new([0; 256]);
+    let mut parsers = Vec::with_capacity(cases.len() + 1);
+
+    parsers.push(default);
+
+    for (bytes, parser) in cases {
+        for b in bytes {
+            if table[*b as usize] == 0 {
+                table[*b as usize] = parsers.len();
+            }
+        }
+        parsers.push(parser);
+    }
+
+    Dispatch(table, parsers)
+}
+
+pub struct Tuple<T, A>(pub T, PhantomData<A>);
+
+#[inline]
+pub fn tuple<T, A>(p: T) -> Tuple<T, A>
+where
+    Tuple<T, A>: Parser<A>,
+{
+    Tuple(p, PhantomData)
+}
+
+// -------------------------------------------------------------------------------------------------
+
 pub struct Opt<E, A>(E, PhantomData<A>)
 where
     E: Parser<A>;
@@ -157,35 +226,105 @@ where
 
 //  -------------------------------------------------------------------------------------------------
 
-pub type TakeWhile = Repeat<Satisfy<Any, u8>, u8>;
+// Byte predicates are evaluated once for each byte value when the parser is created
+
+pub struct TakeWhile(pub ByteSet);
+
+impl Parser<Vec<u8>> for TakeWhile {}
 
 #[inline]
-pub fn take_while<F: 'static>(f: F) -> TakeWhile
+pub fn take_while<F>(f: F) -> TakeWhile
 where
     F: Fn(&u8) -> bool,
 {
-    any().satisfy(f).optrep()
+    TakeWhile(ByteSet::from_fn(f))
 }
 
-pub type TakeOne = Try<Satisfy<Any, u8>, u8>;
+pub struct TakeOne(pub ByteSet);
+
+impl Parser<u8> for TakeOne {}
 
 #[inline]
-pub fn take_one<F: 'static>(f: F) -> TakeOne
+pub fn take_one<F>(f: F) -> TakeOne
 where
     F: Fn(&u8) -> bool,
 {
-    do_try(any().satisfy(f))
+    TakeOne(ByteSet::from_fn(f))
+}
+
+//  -------------------------------------------------------------------------------------------------
+
+pub struct ManyTill<E, A, R, B>(pub E, pub R, PhantomData<A>, PhantomData<B>)
+where
+    E: Parser<A>,
+    R: Parser<B>;
+
+impl<E, A, R, B> Parser<(Vec<A>, B)> for ManyTill<E, A, R, B>
+where
+    E: Parser<A>,
+    R: Parser<B>,
+{
+}
+
+#[inline]
+pub fn many_till<E, A, R, B>(p: E, end: R) -> ManyTill<E, A, R, B>
+where
+    E: Parser<A>,
+    R: Parser<B>,
+{
+    ManyTill(p, end, PhantomData, PhantomData)
+}
+
+pub trait ManyTillOperation<E, A, R, B>
+where
+    E: Parser<A>,
+    R: Parser<B>,
+{
+    fn many_till(self, end: R) -> ManyTill<E, A, R, B>;
+}
+
+impl<E, A, R, B> ManyTillOperation<E, A, R, B> for E
+where
+    E: Parser<A>,
+    R: Parser<B>,
+{
+    #[inline]
+    fn many_till(self, end: R) -> ManyTill<E, A, R, B> {
+        many_till(self, end)
+    }
+}
+
+//  -------------------------------------------------------------------------------------------------
+
+// The borders of the terminator prefixes are computed once for the search
+
+pub struct TakeUntil(pub String, Vec<usize>);
+
+impl Parser<&[u8]> for TakeUntil {}
+
+#[inline]
+pub fn take_until(s: &str) -> TakeUntil {
+    TakeUntil(s.to_string(), borders(s.as_bytes()))
+}
+
+pub struct TakeUntilAny(pub ByteSet);
+
+impl Parser<&[u8]> for TakeUntilAny {}
+
+#[inline]
+pub fn take_until_any(bytes: &[u8]) -> TakeUntilAny {
+    TakeUntilAny(ByteSet::from_bytes(bytes))
 }
 
 // -------------------------------------------------------------------------------------------------
 
-pub struct Skip(pub String);
+pub struct Skip(pub ByteSet);
 
 impl Parser<()> for Skip {}
 
 #[inline]
 pub fn skip(s: String) -> Skip {
-    Skip(s)
+    Skip(ByteSet::from_bytes(s.as_bytes()))
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -212,6 +351,19 @@ where
             }
         }
     }
+
+    fn nullable(&self) -> bool {
+        self.0.nullable() || self.1.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings);
+        self.1.check(warnings);
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::alt(vec![self.0.describe(), self.1.describe()])
+    }
 }
 
 impl<'a, E, R, A> Parsable<'a, A> for Or<E, R, A>
@@ -259,6 +411,19 @@ where
             _ => response(None, r1.o, r1.c),
         }
     }
+
+    fn nullable(&self) -> bool {
+        self.0.nullable() && self.1.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings);
+        self.1.check(warnings);
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::seq(vec![self.0.describe(), self.1.describe()])
+    }
 }
 
 impl<'a, E, A, R, B> Parsable<'a, (A, B)> for And<E, A, R, B>
@@ -286,6 +451,276 @@ where
 
 // -------------------------------------------------------------------------------------------------
 
+macro_rules! nary_parsers {
+    ($($t:ident $a:ident $p:ident $v:ident),+) => {
+        impl<A, $($t),+> Parser<A> for Choice<($($t,)+), A>
+        where
+            $($t: Parser<A>),+
+        {
+        }
+
+        impl<'a, A, $($t),+> Executable<'a, A> for Choice<($($t,)+), A>
+        where
+            $($t: Executable<'a, A> + Parser<A>),+
+        {
+            fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+                let Choice(($($p,)+), _) = self;
+
+                $(
+                    let r = $p.execute(s, o);
+                    if r.v.is_some() || r.c {
+                        return r;
+                    }
+                )+
+
+                r
+            }
+
+            fn nullable(&self) -> bool {
+                let Choice(($($p,)+), _) = self;
+
+                false $(|| $p.nullable())+
+            }
+
+            fn check(&self, warnings: &mut Vec<Warning>) {
+                let Choice(($($p,)+), _) = self;
+
+                $($p.check(warnings);)+
+            }
+
+            fn describe(&self) -> Grammar {
+                let Choice(($($p,)+), _) = self;
+
+                Grammar::alt(vec![$($p.describe()),+])
+            }
+        }
+
+        impl<'a, A, $($t),+> Parsable<'a, A> for Choice<($($t,)+), A>
+        where
+            $($t: Parsable<'a, A> + Parser<A>),+
+        {
+            fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+                let Choice(($($p,)+), _) = self;
+
+                $(
+                    let r = $p.parse_only(s, o);
+                    if r.v.is_some() || r.c {
+                        return r;
+                    }
+                )+
+
+                r
+            }
+        }
+
+        impl<$($t, $a),+> Parser<($($a,)+)> for Tuple<($($t,)+), ($($a,)+)>
+        where
+            $($t: Parser<$a>),+
+        {
+        }
+
+        impl<'a, $($t, $a),+> Executable<'a, ($($a,)+)> for Tuple<($($t,)+), ($($a,)+)>
+        where
+            $($t: Executable<'a, $a> + Parser<$a>),+
+        {
+            fn execute(&self, s: &'a [u8], o: usize) -> Response<($($a,)+)> {
+                let Tuple(($($p,)+), _) = self;
+                let mut offset = o;
+                let mut consumed = false;
+
+                $(
+                    let r = $p.execute(s, offset);
+                    consumed = consumed || r.c;
+                    let $v = match r.v {
+                        Some(v) => v,
+                        None => return response(None, r.o, consumed),
+                    };
+                    offset = r.o;
+                )+
+
+                response(Some(($($v,)+)), offset, consumed)
+            }
+
+            fn nullable(&self) -> bool {
+                let Tuple(($($p,)+), _) = self;
+
+                true $(&& $p.nullable())+
+            }
+
+            fn check(&self, warnings: &mut Vec<Warning>) {
+                let Tuple(($($p,)+), _) = self;
+
+                $($p.check(warnings);)+
+            }
+
+            fn describe(&self) -> Grammar {
+                let Tuple(($($p,)+), _) = self;
+
+                Grammar::seq(vec![$($p.describe()),+])
+            }
+        }
+
+        impl<'a, S, A, $($t),+> StatefulExecutable<'a, A, S> for Choice<($($t,)+), A>
+        where
+            $($t: StatefulExecutable<'a, A, S> + Parser<A>),+,
+            S: Clone,
+        {
+            fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+                let Choice(($($p,)+), _) = self;
+                let saved = state.clone();
+
+                $(
+                    let r = $p.execute_with(s, o, state);
+                    if r.v.is_some() || r.c {
+                        return r;
+                    }
+                    *state = saved.clone();
+                )+
+
+                r
+            }
+        }
+
+        impl<'a, S, $($t, $a),+> StatefulExecutable<'a, ($($a,)+), S> for Tuple<($($t,)+), ($($a,)+)>
+        where
+            $($t: StatefulExecutable<'a, $a, S> + Parser<$a>),+
+        {
+            fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<($($a,)+)> {
+                let Tuple(($($p,)+), _) = self;
+                let mut offset = o;
+                let mut consumed = false;
+
+                $(
+                    let r = $p.execute_with(s, offset, state);
+                    consumed = consumed || r.c;
+                    let $v = match r.v {
+                        Some(v) => v,
+                        None => return response(None, r.o, consumed),
+                    };
+                    offset = r.o;
+                )+
+
+                response(Some(($($v,)+)), offset, consumed)
+            }
+        }
+
+        impl<'a, $($t, $a),+> Parsable<'a, ($($a,)+)> for Tuple<($($t,)+), ($($a,)+)>
+        where
+            $($t: Parsable<'a, $a> + Parser<$a>),+
+        {
+            fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+                let Tuple(($($p,)+), _) = self;
+                let mut offset = o;
+                let mut consumed = false;
+
+                $(
+                    let r = $p.parse_only(s, offset);
+                    consumed = consumed || r.c;
+                    if r.v.is_none() {
+                        return response(None, r.o, consumed);
+                    }
+                    offset = r.o;
+                )+
+
+                response(Some(()), offset, consumed)
+            }
+        }
+    };
+}
+
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6, P7 A7 p7 a7);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6, P7 A7 p7 a7, P8 A8 p8 a8);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6, P7 A7 p7 a7, P8 A8 p8 a8, P9 A9 p9 a9);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6, P7 A7 p7 a7, P8 A8 p8 a8, P9 A9 p9 a9, P10 A10 p10 a10);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6, P7 A7 p7 a7, P8 A8 p8 a8, P9 A9 p9 a9, P10 A10 p10 a10, P11 A11 p11 a11);
+nary_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6, P7 A7 p7 a7, P8 A8 p8 a8, P9 A9 p9 a9, P10 A10 p10 a10, P11 A11 p11 a11, P12 A12 p12 a12);
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, A> Executable<'a, A> for ChoiceVec<'a, A> {
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+        let ChoiceVec(parsers) = self;
+
+        for p in parsers {
+            let r = p.execute(s, o);
+            if r.v.is_some() || r.c {
+                return r;
+            }
+        }
+
+        response(None, o, false)
+    }
+
+    fn nullable(&self) -> bool {
+        self.0.iter().any(|p| p.nullable())
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.iter().for_each(|p| p.check(warnings))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::alt(self.0.iter().map(|p| p.describe()).collect())
+    }
+}
+
+impl<'a, A> Parsable<'a, A> for ChoiceVec<'a, A> {
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, A> Executable<'a, A> for Dispatch<'a, A> {
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+        let Dispatch(table, parsers) = self;
+
+        if o < s.len() {
+            parsers[table[s[o] as usize]].execute(s, o)
+        } else {
+            parsers[0].execute(s, o)
+        }
+    }
+
+    fn nullable(&self) -> bool {
+        self.1.iter().any(|p| p.nullable())
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.1.iter().for_each(|p| p.check(warnings))
+    }
+
+    // Cases are described in their order followed by the default parser
+
+    fn describe(&self) -> Grammar {
+        let Dispatch(_, parsers) = self;
+
+        Grammar::alt(
+            parsers
+                .iter()
+                .skip(1)
+                .chain(parsers.first())
+                .map(|p| p.describe())
+                .collect(),
+        )
+    }
+}
+
+impl<'a, A> Parsable<'a, A> for Dispatch<'a, A> {
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 impl<'a, E, A> Executable<'a, Option<A>> for Opt<E, A>
 where
     E: Executable<'a, A> + Parser<A>,
@@ -300,6 +735,18 @@ where
             None => response(None, o, result.c),
         }
     }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Opt(Box::new(self.0.describe()))
+    }
 }
 
 impl<'a, E, A> Parsable<'a, Option<A>> for Opt<E, A>
@@ -334,7 +781,10 @@ where
         loop {
             let result = p.execute(s, offset);
             match result.v {
-                Some(a) => {
+                Some(_) if result.o == offset && non_consuming() == NonConsuming::Reject => {
+                    return rejected(offset);
+                }
+                Some(a) if result.o != offset => {
                     values.push(a);
                     offset = result.o;
                     consumed = consumed || result.c;
@@ -349,6 +799,28 @@ where
             }
         }
     }
+
+    fn nullable(&self) -> bool {
+        self.0 || self.1.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        if self.1.nullable() {
+            warnings.push(Warning::NullableRepeat);
+        }
+
+        self.1.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        let Repeat(opt, p, _) = self;
+
+        if *opt {
+            Grammar::OptRep(Box::new(p.describe()))
+        } else {
+            Grammar::Rep(Box::new(p.describe()))
+        }
+    }
 }
 
 impl<'a, E, A> Parsable<'a, Vec<A>> for Repeat<E, A>
@@ -364,7 +836,10 @@ where
         loop {
             let r = p.parse_only(s, offset);
             match r.v {
-                Some(_) => {
+                Some(_) if r.o == offset && non_consuming() == NonConsuming::Reject => {
+                    return rejected(offset);
+                }
+                Some(_) if r.o != offset => {
                     offset = r.o;
                     consumed = consumed || r.c;
                 }
@@ -386,20 +861,474 @@ impl<'a> Executable<'a, ()> for Skip {
     fn execute(&self, s: &'a [u8], o: usize) -> Response<()> {
         self.parse_only(s, o)
     }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::OptRep(Box::new(byte_class(&self.0)))
+    }
 }
 
 impl<'a> Parsable<'a, ()> for Skip {
     #[inline]
     fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
-        let Skip(chars) = self;
-        let bytes = chars.as_bytes();
-        let mut n = o;
+        let Skip(bytes) = self;
 
-        while n < s.len() && bytes.contains(&s[n]) {
-            n += 1;
+        response(Some(()), bytes.skip(s, o), false)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a> Executable<'a, Vec<u8>> for TakeWhile {
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<Vec<u8>> {
+        let TakeWhile(bytes) = self;
+
+        if o > s.len() {
+            return response(None, o, false);
         }
 
-        response(Some(()), n, false)
+        let n = bytes.skip(s, o);
+
+        response(Some(s[o..n].to_vec()), n, n > o)
+    }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::OptRep(Box::new(byte_class(&self.0)))
+    }
+}
+
+impl<'a> Parsable<'a, Vec<u8>> for TakeWhile {
+    #[inline]
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        let TakeWhile(bytes) = self;
+
+        if o > s.len() {
+            return response(None, o, false);
+        }
+
+        let n = bytes.skip(s, o);
+
+        response(Some(()), n, n > o)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a> Executable<'a, u8> for TakeOne {
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<u8> {
+        let TakeOne(bytes) = self;
+
+        if o < s.len() && bytes.contains(s[o]) {
+            return response(Some(s[o]), o + 1, true);
+        }
+
+        response(None, o, false)
+    }
+
+    fn describe(&self) -> Grammar {
+        byte_class(&self.0)
+    }
+}
+
+impl<'a> Parsable<'a, u8> for TakeOne {
+    #[inline]
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, R, B> Executable<'a, (Vec<A>, B)> for ManyTill<E, A, R, B>
+where
+    E: Executable<'a, A> + Parser<A>,
+    R: Executable<'a, B> + Parser<B>,
+{
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<(Vec<A>, B)> {
+        let ManyTill(p, end, _, _) = self;
+
+        let mut values: Vec<A> = Vec::with_capacity(13);
+        let mut offset = o;
+        let mut consumed = false;
+
+        loop {
+            let r = end.execute(s, offset);
+            match r.v {
+                Some(b) => return response(Some((values, b)), r.o, consumed || r.c),
+                None if r.c => return response(None, r.o, true),
+                None => {
+                    let r = p.execute(s, offset);
+                    match r.v {
+                        Some(_) if r.o == offset && non_consuming() == NonConsuming::Reject => {
+                            return rejected(offset);
+                        }
+                        Some(a) if r.o != offset => {
+                            values.push(a);
+                            offset = r.o;
+                            consumed = consumed || r.c;
+                        }
+                        _ => return response(None, r.o, consumed || r.c),
+                    }
+                }
+            }
+        }
+    }
+
+    fn nullable(&self) -> bool {
+        self.1.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        if self.0.nullable() {
+            warnings.push(Warning::NullableRepeat);
+        }
+
+        self.0.check(warnings);
+        self.1.check(warnings);
+    }
+
+    fn describe(&self) -> Grammar {
+        let ManyTill(p, end, _, _) = self;
+
+        Grammar::seq(vec![
+            Grammar::OptRep(Box::new(p.describe())),
+            end.describe(),
+        ])
+    }
+}
+
+impl<'a, E, A, R, B> Parsable<'a, (Vec<A>, B)> for ManyTill<E, A, R, B>
+where
+    E: Parsable<'a, A> + Parser<A>,
+    R: Parsable<'a, B> + Parser<B>,
+{
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        let ManyTill(p, end, _, _) = self;
+
+        let mut offset = o;
+        let mut consumed = false;
+
+        loop {
+            let r = end.parse_only(s, offset);
+            match r.v {
+                Some(_) => return response(Some(()), r.o, consumed || r.c),
+                None if r.c => return response(None, r.o, true),
+                None => {
+                    let r = p.parse_only(s, offset);
+                    match r.v {
+                        Some(_) if r.o == offset && non_consuming() == NonConsuming::Reject => {
+                            return rejected(offset);
+                        }
+                        Some(_) if r.o != offset => {
+                            offset = r.o;
+                            consumed = consumed || r.c;
+                        }
+                        _ => return response(None, r.o, consumed || r.c),
+                    }
+                }
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// Knuth-Morris-Pratt search where the border of each needle prefix is the length of its longest
+// proper prefix which is also a suffix. The input is read once and the first byte of the needle
+// is scanned for while no prefix is matched.
+
+fn borders(needle: &[u8]) -> Vec<usize> {
+    let mut borders = vec![0; needle.len()];
+    let mut k = 0;
+
+    for i in 1..needle.len() {
+        while k > 0 && needle[i] != needle[k] {
+            k = borders[k - 1];
+        }
+        if needle[i] == needle[k] {
+            k += 1;
+        }
+        borders[i] = k;
+    }
+
+    borders
+}
+
+fn find_bytes(s: &[u8], needle: &[u8], borders: &[usize]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+
+    let mut n = 0;
+    let mut k = 0;
+
+    while n < s.len() {
+        if k == 0 {
+            n += s[n..].iter().position(|b| *b == needle[0])?;
+        }
+        while k > 0 && s[n] != needle[k] {
+            k = borders[k - 1];
+        }
+        if s[n] == needle[k] {
+            k += 1;
+        }
+        n += 1;
+        if k == needle.len() {
+            return Some(n - k);
+        }
+    }
+
+    None
+}
+
+impl<'a> Executable<'a, &'a [u8]> for TakeUntil {
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<&'a [u8]> {
+        let r = self.parse_only(s, o);
+
+        match r.v {
+            Some(_) => response(Some(&s[o..r.o]), r.o, r.c),
+            _ => response(None, r.o, r.c),
+        }
+    }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Until(Box::new(Grammar::Literal(self.0.clone())))
+    }
+}
+
+impl<'a> Parsable<'a, &'a [u8]> for TakeUntil {
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        let TakeUntil(terminator, borders) = self;
+
+        match find_bytes(&s[o.min(s.len())..], terminator.as_bytes(), borders) {
+            Some(n) => response(Some(()), o + n, n > 0),
+            None => response(None, o, false),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a> Executable<'a, &'a [u8]> for TakeUntilAny {
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<&'a [u8]> {
+        let r = self.parse_only(s, o);
+
+        match r.v {
+            Some(_) => response(Some(&s[o..r.o]), r.o, r.c),
+            _ => response(None, r.o, r.c),
+        }
+    }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Until(Box::new(byte_class(&self.0)))
+    }
+}
+
+impl<'a> Parsable<'a, &'a [u8]> for TakeUntilAny {
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        let TakeUntilAny(bytes) = self;
+
+        match bytes.find(s, o) {
+            Some(n) => response(Some(()), n, n > o),
+            None => response(None, o, false),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Stateful parser execution
+// -------------------------------------------------------------------------------------------------
+
+// Backtracking parsers clone the state before each attempt in order to restore it, so the
+// state should be cheap to clone.
+
+impl Stateless for TakeUntil {}
+
+impl Stateless for TakeUntilAny {}
+
+impl Stateless for Skip {}
+
+impl Stateless for TakeWhile {}
+
+impl Stateless for TakeOne {}
+
+// Boxed alternatives are stateless parsers
+
+impl<'a, A> Stateless for ChoiceVec<'a, A> {}
+
+impl<'a, A> Stateless for Dispatch<'a, A> {}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, R, A, S> StatefulExecutable<'a, A, S> for Or<E, R, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    R: StatefulExecutable<'a, A, S> + Parser<A>,
+    S: Clone,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Or(p1, p2, _) = self;
+        let saved = state.clone();
+        let r = p1.execute_with(s, o, state);
+
+        match r.v {
+            Some(_) => r,
+            _ => {
+                if r.c {
+                    response(None, r.o, r.c)
+                } else {
+                    *state = saved;
+                    p2.execute_with(s, o, state)
+                }
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, R, B, S> StatefulExecutable<'a, (A, B), S> for And<E, A, R, B>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    R: StatefulExecutable<'a, B, S> + Parser<B>,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<(A, B)> {
+        let And(p1, p2, _, _) = self;
+        let r1 = p1.execute_with(s, o, state);
+
+        match r1.v {
+            Some(a1) => {
+                let r2 = p2.execute_with(s, r1.o, state);
+
+                match r2.v {
+                    Some(a2) => response(Some((a1, a2)), r2.o, r1.c || r2.c),
+                    _ => response(None, r2.o, r1.c || r2.c),
+                }
+            }
+            _ => response(None, r1.o, r1.c),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, S> StatefulExecutable<'a, Option<A>, S> for Opt<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    S: Clone,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<Option<A>> {
+        let Opt(p, _) = self;
+        let saved = state.clone();
+        let result = p.execute_with(s, o, state);
+
+        match result.v {
+            Some(value) => response(Some(Some(value)), result.o, result.c),
+            None if !result.c => {
+                *state = saved;
+                response(Some(None), o, result.c)
+            }
+            None => response(None, o, result.c),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, S> StatefulExecutable<'a, Vec<A>, S> for Repeat<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    S: Clone,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<Vec<A>> {
+        let Repeat(opt, p, _) = self;
+
+        let mut values: Vec<A> = Vec::with_capacity(13);
+        let mut offset = o;
+        let mut consumed = false;
+
+        loop {
+            let saved = state.clone();
+            let result = p.execute_with(s, offset, state);
+            match result.v {
+                Some(_) if result.o == offset && non_consuming() == NonConsuming::Reject => {
+                    return rejected(offset);
+                }
+                Some(a) if result.o != offset => {
+                    values.push(a);
+                    offset = result.o;
+                    consumed = consumed || result.c;
+                }
+                _ => {
+                    *state = saved;
+
+                    if *opt || offset - o > 0 {
+                        return response(Some(values), offset, consumed);
+                    }
+
+                    return response(None, offset, consumed);
+                }
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, R, B, S> StatefulExecutable<'a, (Vec<A>, B), S> for ManyTill<E, A, R, B>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    R: StatefulExecutable<'a, B, S> + Parser<B>,
+    S: Clone,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<(Vec<A>, B)> {
+        let ManyTill(p, end, _, _) = self;
+
+        let mut values: Vec<A> = Vec::with_capacity(13);
+        let mut offset = o;
+        let mut consumed = false;
+
+        loop {
+            let saved = state.clone();
+            let r = end.execute_with(s, offset, state);
+            match r.v {
+                Some(b) => return response(Some((values, b)), r.o, consumed || r.c),
+                None if r.c => return response(None, r.o, true),
+                None => {
+                    *state = saved;
+
+                    let r = p.execute_with(s, offset, state);
+                    match r.v {
+                        Some(_) if r.o == offset && non_consuming() == NonConsuming::Reject => {
+                            return rejected(offset);
+                        }
+                        Some(a) if r.o != offset => {
+                            values.push(a);
+                            offset = r.o;
+                            consumed = consumed || r.c;
+                        }
+                        _ => return response(None, r.o, consumed || r.c),
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -0,0 +1,326 @@
+use crate::parsers::charset::*;
+use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+
+// -------------------------------------------------------------------------------------------------
+// Sentence generation definition
+// -------------------------------------------------------------------------------------------------
+
+// Depth bounds the number of nested rule expansions and repeat the number of repetitions. Once
+// the depth is exhausted alternatives are tried until one of them ends without expanding a rule.
+// Since choices are ordered a sentence can still be rejected when a previous alternative accepts
+// a prefix of the generated one.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Generation {
+    pub seed: u64,
+    pub depth: usize,
+    pub repeat: usize,
+}
+
+impl Default for Generation {
+    fn default() -> Self {
+        Generation {
+            seed: 0,
+            depth: 16,
+            repeat: 4,
+        }
+    }
+}
+
+pub struct Generator {
+    rules: Vec<(String, Grammar)>,
+    random: Random,
+    depth: usize,
+    repeat: usize,
+}
+
+pub fn generator<'a, E, A>(p: &E, generation: Generation) -> Generator
+where
+    E: Executable<'a, A>,
+{
+    Generator::new(&p.describe(), generation)
+}
+
+impl Generator {
+    pub fn new(grammar: &Grammar, generation: Generation) -> Generator {
+        Generator {
+            rules: grammar.rules("grammar"),
+            random: Random(generation.seed),
+            depth: generation.depth,
+            repeat: generation.repeat,
+        }
+    }
+
+    // None is returned when the grammar cannot be generated like dynamic parsers
+    pub fn sentence(&mut self) -> Option<Vec<u8>> {
+        let grammar = self.rules[0].1.clone();
+        let mut sentence = Vec::new();
+
+        if self.generate(&grammar, self.depth, &mut sentence) {
+            Some(sentence)
+        } else {
+            None
+        }
+    }
+
+    fn generate(&mut self, grammar: &Grammar, depth: usize, sentence: &mut Vec<u8>) -> bool {
+        match grammar {
+            Grammar::Empty | Grammar::Eos | Grammar::Lookahead(_) => true,
+            Grammar::Fail | Grammar::Dynamic => false,
+            Grammar::Any => {
+                sentence.push(self.random.below(256) as u8);
+                true
+            }
+            Grammar::Literal(s) => {
+                sentence.extend_from_slice(s.as_bytes());
+                true
+            }
+            Grammar::CharClass(spec) => match self.class(spec) {
+                Some(c) => {
+                    sentence.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                    true
+                }
+                None => false,
+            },
+            Grammar::ByteClass(ranges) => match self.byte(ranges) {
+                Some(b) => {
+                    sentence.push(b);
+                    true
+                }
+                None => false,
+            },
+            Grammar::Seq(items) => items.iter().all(|g| self.generate(g, depth, sentence)),
+            Grammar::Alt(items) => self.alternative(items, depth, sentence),
+            Grammar::Rep(g) => {
+                let count = 1 + self.count(depth);
+                (0..count).all(|_| self.generate(g, depth, sentence))
+            }
+            Grammar::OptRep(g) => {
+                let count = self.count(depth);
+                (0..count).all(|_| self.generate(g, depth, sentence))
+            }
+            Grammar::Opt(g) => {
+                let length = sentence.len();
+
+                if depth > 0 && self.random.below(2) == 0 && self.generate(g, depth, sentence) {
+                    return true;
+                }
+
+                sentence.truncate(length);
+                true
+            }
+            Grammar::Until(g) => self.until(g, sentence),
+            // The predicate may reject the generated sentence
+            Grammar::Filter(g) => self.generate(g, depth, sentence),
+            Grammar::Terminal(name) => self.terminal(name, sentence),
+            Grammar::Named(_, g) => depth > 0 && self.generate(g, depth - 1, sentence),
+            Grammar::Rule(name) => {
+                let rule = self
+                    .rules
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, g)| g.clone());
+
+                match rule {
+                    Some(g) => depth > 0 && self.generate(&g, depth - 1, sentence),
+                    None => false,
+                }
+            }
+        }
+    }
+
+    // Alternatives are tried in a random order until one of them is generated
+    fn alternative(&mut self, items: &[Grammar], depth: usize, sentence: &mut Vec<u8>) -> bool {
+        let length = sentence.len();
+        let mut indexes: Vec<usize> = (0..items.len()).collect();
+
+        while !indexes.is_empty() {
+            let i = indexes.remove(self.random.below(indexes.len()));
+
+            if self.generate(&items[i], depth, sentence) {
+                return true;
+            }
+
+            sentence.truncate(length);
+        }
+
+        false
+    }
+
+    fn count(&mut self, depth: usize) -> usize {
+        if depth == 0 {
+            0
+        } else {
+            self.random.below(self.repeat + 1)
+        }
+    }
+
+    // Negated classes are only generated with printable ASCII characters
+    fn class(&mut self, spec: &str) -> Option<char> {
+        let (negated, ranges) = class_items(spec).ok()?;
+
+        if negated {
+            let candidates: Vec<char> = (' '..='~')
+                .filter(|c| ranges.iter().all(|(l, h)| c < l || c > h))
+                .collect();
+
+            if candidates.is_empty() {
+                None
+            } else {
+                Some(candidates[self.random.below(candidates.len())])
+            }
+        } else {
+            let size: u32 = ranges.iter().map(|(l, h)| *h as u32 - *l as u32 + 1).sum();
+            let mut n = self.random.below(size as usize) as u32;
+
+            for (l, h) in ranges {
+                let width = h as u32 - l as u32 + 1;
+
+                if n < width {
+                    return char::from_u32(l as u32 + n);
+                }
+                n -= width;
+            }
+
+            None
+        }
+    }
+
+    // Bytes are emitted as is and sets holding most of them are generated like negated classes
+    // with printable ASCII characters when they hold some
+    fn byte(&mut self, ranges: &[(u8, u8)]) -> Option<u8> {
+        let contains = |b: u8| ranges.iter().any(|(l, h)| *l <= b && b <= *h);
+        let size: usize = ranges.iter().map(|(l, h)| (h - l) as usize + 1).sum();
+        let printable: Vec<u8> = (b' '..=b'~').filter(|b| contains(*b)).collect();
+        let candidates: Vec<u8> = if size > 128 && !printable.is_empty() {
+            printable
+        } else {
+            (0..=255u8).filter(|b| contains(*b)).collect()
+        };
+
+        if candidates.is_empty() {
+            None
+        } else {
+            Some(candidates[self.random.below(candidates.len())])
+        }
+    }
+
+    // Bytes are printable ASCII characters which cannot start the terminator so the parser stops
+    // at the following item
+    fn until(&mut self, terminator: &Grammar, sentence: &mut Vec<u8>) -> bool {
+        let mut candidates = Vec::new();
+
+        for c in b' '..=b'~' {
+            match starts(terminator, c) {
+                Some(true) => (),
+                Some(false) => candidates.push(c),
+                None => return false,
+            }
+        }
+
+        if !candidates.is_empty() {
+            for _ in 0..self.random.below(self.repeat + 1) {
+                sentence.push(candidates[self.random.below(candidates.len())]);
+            }
+        }
+
+        true
+    }
+
+    fn terminal(&mut self, name: &str, sentence: &mut Vec<u8>) -> bool {
+        match name {
+            "float" => {
+                match self.random.below(3) {
+                    0 => sentence.push(b'+'),
+                    1 => sentence.push(b'-'),
+                    _ => (),
+                }
+                self.digits(sentence);
+                if self.random.below(2) == 0 {
+                    sentence.push(b'.');
+                    self.digits(sentence);
+                }
+                true
+            }
+            "delimited_string" => {
+                sentence.push(b'"');
+                for _ in 0..self.random.below(self.repeat + 1) {
+                    sentence.push(self.printable(b"\"\\"));
+                }
+                sentence.push(b'"');
+                true
+            }
+            "delimited_char" => {
+                sentence.push(b'\'');
+                sentence.push(self.printable(b"'\\"));
+                sentence.push(b'\'');
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn digits(&mut self, sentence: &mut Vec<u8>) {
+        for _ in 0..=self.random.below(self.repeat) {
+            sentence.push(b'0' + self.random.below(10) as u8);
+        }
+    }
+
+    fn printable(&mut self, excluded: &[u8]) -> u8 {
+        loop {
+            let c = b' ' + self.random.below(95) as u8;
+
+            if !excluded.contains(&c) {
+                return c;
+            }
+        }
+    }
+}
+
+// None is returned when the first bytes of the grammar are not known
+fn starts(grammar: &Grammar, c: u8) -> Option<bool> {
+    match grammar {
+        Grammar::Literal(s) => Some(s.as_bytes().first().is_none_or(|b| *b == c)),
+        Grammar::CharClass(spec) => {
+            let (negated, ranges) = class_items(spec).ok()?;
+            let c = c as char;
+
+            Some(negated != ranges.iter().any(|(l, h)| *l <= c && c <= *h))
+        }
+        Grammar::Alt(items) => items
+            .iter()
+            .try_fold(false, |a, g| Some(a || starts(g, c)?)),
+        _ => None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Seeded pseudo random numbers
+// -------------------------------------------------------------------------------------------------
+
+// SplitMix64 is small, fast and good enough for input generation
+
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform enough for small bounds, zero when the bound is zero
+    fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            0
+        } else {
+            (self.next() % bound as u64) as usize
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,356 @@
+use core::marker::PhantomData;
+use std::cell::RefCell;
+
+use crate::parsers::bytes::*;
+use crate::parsers::execution::*;
+use crate::parsers::lint::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Grammar definition
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Grammar {
+    // Succeeds without consuming input
+    Empty,
+    Fail,
+    Any,
+    Eos,
+    Literal(String),
+    // Character class using the `char_class` syntax
+    CharClass(String),
+    // Inclusive byte ranges for sets holding bytes which are not ASCII
+    ByteClass(Vec<(u8, u8)>),
+    Seq(Vec<Grammar>),
+    Alt(Vec<Grammar>),
+    // One or more
+    Rep(Box<Grammar>),
+    // Zero or more
+    OptRep(Box<Grammar>),
+    Opt(Box<Grammar>),
+    Lookahead(Box<Grammar>),
+    // Any bytes up to the first match of a terminator which is not consumed
+    Until(Box<Grammar>),
+    // Filtered by a predicate which cannot be described
+    Filter(Box<Grammar>),
+    // Primitive parser without structure like `float`
+    Terminal(String),
+    // Definition of a named rule
+    Named(String, Box<Grammar>),
+    // Reference to a named rule being described
+    Rule(String),
+    // Parser depending on parsed values like `bind`
+    Dynamic,
+}
+
+impl Grammar {
+    // Nested sequences and alternatives are flattened
+
+    pub fn seq(items: Vec<Grammar>) -> Grammar {
+        let mut flat = Vec::with_capacity(items.len());
+
+        for item in items {
+            match item {
+                Grammar::Seq(nested) => flat.extend(nested),
+                Grammar::Empty => (),
+                item => flat.push(item),
+            }
+        }
+
+        match flat.len() {
+            0 => Grammar::Empty,
+            1 => flat.pop().unwrap(),
+            _ => Grammar::Seq(flat),
+        }
+    }
+
+    pub fn alt(items: Vec<Grammar>) -> Grammar {
+        let mut flat = Vec::with_capacity(items.len());
+
+        for item in items {
+            match item {
+                Grammar::Alt(nested) => flat.extend(nested),
+                item => flat.push(item),
+            }
+        }
+
+        match flat.len() {
+            0 => Grammar::Fail,
+            1 => flat.pop().unwrap(),
+            _ => Grammar::Alt(flat),
+        }
+    }
+
+    // Named grammars are extracted as rule definitions and replaced by references. Rules are
+    // given in their order of appearance and the root is named unless it is already.
+
+    pub fn rules(&self, root: &str) -> Vec<(String, Grammar)> {
+        let mut rules = Vec::new();
+
+        if let Grammar::Named(..) = self {
+            extract(self, &mut rules);
+        } else {
+            let grammar = extract(self, &mut rules);
+            rules.insert(0, (root.to_string(), grammar));
+        }
+
+        rules
+    }
+}
+
+fn extract(grammar: &Grammar, rules: &mut Vec<(String, Grammar)>) -> Grammar {
+    let boxed = |g: &Grammar, rules: &mut Vec<(String, Grammar)>| Box::new(extract(g, rules));
+
+    match grammar {
+        Grammar::Seq(items) => Grammar::Seq(items.iter().map(|g| extract(g, rules)).collect()),
+        Grammar::Alt(items) => Grammar::Alt(items.iter().map(|g| extract(g, rules)).collect()),
+        Grammar::Rep(g) => Grammar::Rep(boxed(g, rules)),
+        Grammar::OptRep(g) => Grammar::OptRep(boxed(g, rules)),
+        Grammar::Opt(g) => Grammar::Opt(boxed(g, rules)),
+        Grammar::Lookahead(g) => Grammar::Lookahead(boxed(g, rules)),
+        Grammar::Until(g) => Grammar::Until(boxed(g, rules)),
+        Grammar::Filter(g) => Grammar::Filter(boxed(g, rules)),
+        Grammar::Named(name, g) => {
+            if rules.iter().all(|(n, _)| n != name) {
+                let n = rules.len();
+                rules.push((name.clone(), Grammar::Empty));
+                rules[n].1 = extract(g, rules);
+            }
+
+            Grammar::Rule(name.clone())
+        }
+        g => g.clone(),
+    }
+}
+
+pub fn describe<'a, E, A>(p: &E) -> Grammar
+where
+    E: Executable<'a, A>,
+{
+    p.describe()
+}
+
+// -------------------------------------------------------------------------------------------------
+// Character class rendering
+// -------------------------------------------------------------------------------------------------
+
+// Other characters, control ones included, are kept as is so the spec is accepted by `char_class`
+fn class_char(c: char) -> String {
+    match c {
+        '\\' | '-' | '^' | ']' => format!("\\{}", c),
+        c => c.to_string(),
+    }
+}
+
+// Consecutive characters are rendered as ranges
+pub(crate) fn class_ranges(ranges: &[(char, char)]) -> String {
+    ranges
+        .iter()
+        .map(|(start, end)| match *end as u32 - *start as u32 {
+            0 => class_char(*start),
+            1 => format!("{}{}", class_char(*start), class_char(*end)),
+            _ => format!("{}-{}", class_char(*start), class_char(*end)),
+        })
+        .collect()
+}
+
+// A set holding a byte which is not ASCII is not a character class since characters are UTF-8
+// encoded
+
+pub(crate) fn byte_class(set: &ByteSet) -> Grammar {
+    let mut ranges: Vec<(u8, u8)> = Vec::new();
+
+    for b in 0..=255u8 {
+        if set.contains(b) {
+            match ranges.last_mut() {
+                Some((_, end)) if *end as u32 + 1 == b as u32 => *end = b,
+                _ => ranges.push((b, b)),
+            }
+        }
+    }
+
+    match ranges.last() {
+        Some((_, end)) if !end.is_ascii() => Grammar::ByteClass(ranges),
+        _ => {
+            let ranges: Vec<(char, char)> = ranges
+                .iter()
+                .map(|(start, end)| (*start as char, *end as char))
+                .collect();
+
+            Grammar::CharClass(class_ranges(&ranges))
+        }
+    }
+}
+
+// Byte ranges are rendered with `#xN` for bytes which are not printable or are special and the
+// class is negated when it holds most of the bytes
+
+pub(crate) fn byte_ranges(ranges: &[(u8, u8)]) -> String {
+    let size: usize = ranges.iter().map(|(l, h)| (h - l) as usize + 1).sum();
+    let negated = size > 128;
+    let mut items: Vec<(u8, u8)> = Vec::new();
+
+    for b in 0..=255u8 {
+        if ranges.iter().any(|(l, h)| *l <= b && b <= *h) != negated {
+            match items.last_mut() {
+                Some((_, end)) if *end as u32 + 1 == b as u32 => *end = b,
+                _ => items.push((b, b)),
+            }
+        }
+    }
+
+    let byte = |b: u8| match b {
+        b'[' | b']' | b'-' | b'^' | b'\\' | b'#' => format!("#x{:02X}", b),
+        b if b.is_ascii_graphic() => (b as char).to_string(),
+        b => format!("#x{:02X}", b),
+    };
+    let items: String = items
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                byte(*start)
+            } else {
+                format!("{}-{}", byte(*start), byte(*end))
+            }
+        })
+        .collect();
+
+    format!("[{}{}]", if negated { "^" } else { "" }, items)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Recursive descriptions
+// -------------------------------------------------------------------------------------------------
+
+thread_local! {
+    static DESCRIBING: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+// A recursive parser like `lazy` or `rule` is expanded at most twice: a named parser found again
+// during the second expansion is described by a reference to its rule, otherwise the recursion
+// is dynamic.
+
+pub(crate) fn expanding<F>(key: String, f: F) -> Grammar
+where
+    F: FnOnce() -> Grammar,
+{
+    let depth = DESCRIBING.with(|d| d.borrow().iter().filter(|k| **k == key).count());
+
+    if depth >= 2 {
+        return Grammar::Dynamic;
+    }
+
+    DESCRIBING.with(|d| d.borrow_mut().push(key));
+    let grammar = f();
+    DESCRIBING.with(|d| d.borrow_mut().pop());
+
+    grammar
+}
+
+fn naming<F>(name: &str, f: F) -> Grammar
+where
+    F: FnOnce() -> Grammar,
+{
+    let key = format!("rule {}", name);
+
+    if DESCRIBING.with(|d| d.borrow().contains(&key)) {
+        return Grammar::Rule(name.to_string());
+    }
+
+    DESCRIBING.with(|d| d.borrow_mut().push(key));
+    let grammar = Grammar::Named(name.to_string(), Box::new(f()));
+    DESCRIBING.with(|d| d.borrow_mut().pop());
+
+    grammar
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+pub struct Named<E, A>(pub String, pub E, PhantomData<A>)
+where
+    E: Parser<A>;
+
+impl<E, A> Parser<A> for Named<E, A> where E: Parser<A> {}
+
+#[inline]
+pub fn named<E, A>(name: &str, p: E) -> Named<E, A>
+where
+    E: Parser<A>,
+{
+    Named(name.to_string(), p, PhantomData)
+}
+
+pub trait NamedOperation<E, A>
+where
+    E: Parser<A>,
+{
+    fn named(self, name: &str) -> Named<E, A>;
+}
+
+impl<E, A> NamedOperation<E, A> for E
+where
+    E: Parser<A>,
+{
+    #[inline]
+    fn named(self, name: &str) -> Named<E, A> {
+        named(name, self)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A> Executable<'a, A> for Named<E, A>
+where
+    E: Executable<'a, A> + Parser<A>,
+{
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+        self.1.execute(s, o)
+    }
+
+    fn nullable(&self) -> bool {
+        self.1.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.1.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        let Named(name, p, _) = self;
+
+        naming(name, || p.describe())
+    }
+}
+
+impl<'a, E, A> Parsable<'a, A> for Named<E, A>
+where
+    E: Parsable<'a, A> + Parser<A>,
+{
+    #[inline]
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.1.parse_only(s, o)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Stateful parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, S> StatefulExecutable<'a, A, S> for Named<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+{
+    #[inline]
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        self.1.execute_with(s, o, state)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
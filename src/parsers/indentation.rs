@@ -0,0 +1,239 @@
+use core::marker::PhantomData;
+
+use crate::parsers::execution::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Indentation stack definition
+// -------------------------------------------------------------------------------------------------
+
+pub trait IndentationStack {
+    fn indentations(&mut self) -> &mut Vec<usize>;
+}
+
+impl IndentationStack for Vec<usize> {
+    #[inline]
+    fn indentations(&mut self) -> &mut Vec<usize> {
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+pub struct Column();
+
+impl Parser<usize> for Column {}
+
+#[inline]
+pub fn column() -> Column {
+    Column()
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub struct Block<E, A>(pub E, PhantomData<A>)
+where
+    E: Parser<A>;
+
+impl<E, A> Parser<Vec<A>> for Block<E, A> where E: Parser<A> {}
+
+#[inline]
+pub fn block<E, A>(p: E) -> Block<E, A>
+where
+    E: Parser<A>,
+{
+    Block(p, PhantomData)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub struct Aligned<E, A>(pub E, PhantomData<A>)
+where
+    E: Parser<A>;
+
+impl<E, A> Parser<A> for Aligned<E, A> where E: Parser<A> {}
+
+#[inline]
+pub fn aligned<E, A>(p: E) -> Aligned<E, A>
+where
+    E: Parser<A>,
+{
+    Aligned(p, PhantomData)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub struct Indented<E, A>(pub E, PhantomData<A>)
+where
+    E: Parser<A>;
+
+impl<E, A> Parser<A> for Indented<E, A> where E: Parser<A> {}
+
+#[inline]
+pub fn indented<E, A>(p: E) -> Indented<E, A>
+where
+    E: Parser<A>,
+{
+    Indented(p, PhantomData)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Offset based layout
+// -------------------------------------------------------------------------------------------------
+
+#[inline]
+fn column_at(s: &[u8], o: usize) -> usize {
+    match s[..o].iter().rposition(|b| *b == b'\n') {
+        Some(n) => o - n - 1,
+        None => o,
+    }
+}
+
+#[inline]
+fn skip_blanks(s: &[u8], o: usize) -> usize {
+    let mut n = o;
+
+    while n < s.len() && (s[n] == b' ' || s[n] == b'\t' || s[n] == b'\r' || s[n] == b'\n') {
+        n += 1;
+    }
+
+    n
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a> Executable<'a, usize> for Column {
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<usize> {
+        response(Some(column_at(s, o.min(s.len()))), o, false)
+    }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+}
+
+impl<'a> Parsable<'a, usize> for Column {
+    #[inline]
+    fn parse_only(&self, _: &'a [u8], o: usize) -> Response<()> {
+        response(Some(()), o, false)
+    }
+}
+
+impl Stateless for Column {}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, S> StatefulExecutable<'a, Vec<A>, S> for Block<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    S: IndentationStack + Clone,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<Vec<A>> {
+        let Block(p, _) = self;
+        let mut next = skip_blanks(s, o);
+        let indentation = column_at(s, next);
+
+        if next >= s.len() {
+            return response(None, o, false);
+        }
+
+        if let Some(parent) = state.indentations().last() {
+            if indentation <= *parent {
+                return response(None, o, false);
+            }
+        }
+
+        state.indentations().push(indentation);
+
+        let mut values: Vec<A> = Vec::with_capacity(13);
+        let mut offset = o;
+        let mut consumed = false;
+
+        loop {
+            let saved = state.clone();
+            let r = p.execute_with(s, next, state);
+
+            match r.v {
+                Some(a) => {
+                    values.push(a);
+                    offset = r.o;
+                    consumed = consumed || r.c;
+                }
+                None if r.c => {
+                    state.indentations().pop();
+                    return response(None, r.o, true);
+                }
+                None => {
+                    *state = saved;
+                    break;
+                }
+            }
+
+            // Siblings start on a new line at the block indentation
+            next = skip_blanks(s, offset);
+
+            if next >= s.len()
+                || column_at(s, next) != indentation
+                || !s[offset..next].contains(&b'\n')
+            {
+                break;
+            }
+        }
+
+        state.indentations().pop();
+
+        if values.is_empty() {
+            return response(None, o, false);
+        }
+
+        response(Some(values), offset, consumed)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, S> StatefulExecutable<'a, A, S> for Aligned<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    S: IndentationStack,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Aligned(p, _) = self;
+        let next = skip_blanks(s, o);
+        let indentation = column_at(s, next);
+
+        match state.indentations().last() {
+            Some(current) if *current != indentation => response(None, o, false),
+            None if indentation != 0 => response(None, o, false),
+            _ => p.execute_with(s, next, state),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, S> StatefulExecutable<'a, A, S> for Indented<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    S: IndentationStack,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Indented(p, _) = self;
+        let next = skip_blanks(s, o);
+        let indentation = column_at(s, next);
+
+        match state.indentations().last() {
+            Some(current) if *current >= indentation => response(None, o, false),
+            None if indentation == 0 => response(None, o, false),
+            _ => p.execute_with(s, next, state),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,150 @@
+use core::marker::PhantomData;
+
+use crate::parsers::context::*;
+use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+use crate::parsers::lint::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Labelled failure definition
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure {
+    pub offset: usize,
+    pub label: String,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Labelled failure context
+// -------------------------------------------------------------------------------------------------
+
+// Only the furthest failure is kept and the innermost label wins for a given offset
+
+pub(crate) fn record(offset: usize, label: &str) {
+    state(|state| {
+        if let Some(current) = state.failure.borrow_mut().as_mut() {
+            let furthest = match current {
+                Some(c) => c.offset < offset,
+                None => true,
+            };
+
+            if furthest {
+                *current = Some(Failure {
+                    offset,
+                    label: label.to_string(),
+                });
+            }
+        }
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+pub struct Label<E, A>(pub String, pub E, PhantomData<A>)
+where
+    E: Parser<A>;
+
+impl<E, A> Parser<A> for Label<E, A> where E: Parser<A> {}
+
+#[inline]
+pub fn label<E, A>(label: &str, p: E) -> Label<E, A>
+where
+    E: Parser<A>,
+{
+    Label(label.to_string(), p, PhantomData)
+}
+
+pub trait LabelOperation<E, A>
+where
+    E: Parser<A>,
+{
+    fn label(self, label: &str) -> Label<E, A>;
+}
+
+impl<E, A> LabelOperation<E, A> for E
+where
+    E: Parser<A>,
+{
+    #[inline]
+    fn label(self, l: &str) -> Label<E, A> {
+        label(l, self)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A> Executable<'a, A> for Label<E, A>
+where
+    E: Executable<'a, A> + Parser<A>,
+{
+    #[inline]
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+        let Label(label, p, _) = self;
+        let r = p.execute(s, o);
+
+        if r.v.is_none() {
+            record(o, label);
+        }
+
+        r
+    }
+
+    fn nullable(&self) -> bool {
+        self.1.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.1.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        self.1.describe()
+    }
+}
+
+impl<'a, E, A> Parsable<'a, A> for Label<E, A>
+where
+    E: Parsable<'a, A> + Parser<A>,
+{
+    #[inline]
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        let Label(label, p, _) = self;
+        let r = p.parse_only(s, o);
+
+        if r.v.is_none() {
+            record(o, label);
+        }
+
+        r
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Stateful parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, S> StatefulExecutable<'a, A, S> for Label<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+{
+    #[inline]
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Label(label, p, _) = self;
+        let r = p.execute_with(s, o, state);
+
+        if r.v.is_none() {
+            record(o, label);
+        }
+
+        r
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,99 @@
+use crate::parsers::context::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Limits definition
+// -------------------------------------------------------------------------------------------------
+
+// Depth counts nested Lazy and Parsec executions while fuel is consumed by each of them
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limits {
+    pub depth: usize,
+    pub fuel: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            depth: usize::MAX,
+            fuel: usize::MAX,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LimitError {
+    // Maximum depth reached at the given offset
+    Depth(usize),
+    // Fuel exhausted at the given offset
+    Fuel(usize),
+}
+
+// -------------------------------------------------------------------------------------------------
+// Limits budget
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+pub(crate) struct Budget {
+    depth: usize,
+    fuel: usize,
+    pub(crate) error: Option<LimitError>,
+}
+
+impl Budget {
+    pub(crate) fn new(limits: Limits) -> Self {
+        Budget {
+            depth: limits.depth,
+            fuel: limits.fuel,
+            error: None,
+        }
+    }
+}
+
+// Once a limit is reached every guarded execution is rejected and consumed
+// in order to stop backtracking as soon as possible.
+
+#[inline]
+pub(crate) fn guarded<A, F>(o: usize, f: F) -> Response<A>
+where
+    F: FnOnce() -> Response<A>,
+{
+    let entered = state(|state| match state.budget.get() {
+        None => None,
+        Some(mut budget) => {
+            if budget.error.is_none() {
+                if budget.depth == 0 {
+                    budget.error = Some(LimitError::Depth(o));
+                } else if budget.fuel == 0 {
+                    budget.error = Some(LimitError::Fuel(o));
+                } else {
+                    budget.depth -= 1;
+                    budget.fuel -= 1;
+                }
+            }
+
+            state.budget.set(Some(budget));
+            Some(budget.error.is_none())
+        }
+    });
+
+    match entered {
+        None => f(),
+        Some(false) => response(None, o, true),
+        Some(true) => {
+            let result = f();
+
+            state(|state| {
+                if let Some(mut budget) = state.budget.get() {
+                    budget.depth += 1;
+                    state.budget.set(Some(budget));
+                }
+            });
+
+            result
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
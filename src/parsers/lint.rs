@@ -0,0 +1,85 @@
+use std::cell::RefCell;
+
+use crate::parsers::context::*;
+use crate::parsers::execution::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Grammar lint definition
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Warning {
+    // A repeated parser accepting the empty input loops or stops without consuming input
+    NullableRepeat,
+}
+
+pub fn lint<'a, E, A>(p: &E) -> Vec<Warning>
+where
+    E: Executable<'a, A>,
+{
+    let mut warnings = Vec::new();
+
+    p.check(&mut warnings);
+
+    warnings
+}
+
+// -------------------------------------------------------------------------------------------------
+// Recursive analysis
+// -------------------------------------------------------------------------------------------------
+
+thread_local! {
+    static ANALYSING: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+// A recursive parser like `lazy` is analysed once: the recursive occurrence gives the default
+// result, like a parser which is not nullable and without warnings.
+
+pub(crate) fn analysing<R, F>(key: String, default: R, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    if ANALYSING.with(|a| a.borrow().contains(&key)) {
+        return default;
+    }
+
+    ANALYSING.with(|a| a.borrow_mut().push(key));
+    let result = f();
+    ANALYSING.with(|a| a.borrow_mut().pop());
+
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+// Non consuming repetition policy
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonConsuming {
+    // Fail and commit the repetition
+    Reject,
+    // Stop the repetition as if the repeated parser failed
+    Stop,
+}
+
+// Repetitions are rejected unless an execution context selects another policy
+
+#[inline]
+pub(crate) fn non_consuming() -> NonConsuming {
+    state(|state| state.non_consuming.get())
+}
+
+// A rejected repetition is committed and the first offset is reported by the context
+
+pub(crate) fn rejected<A>(o: usize) -> Response<A> {
+    state(|state| {
+        if state.rejected.get().is_none() {
+            state.rejected.set(Some(o));
+        }
+    });
+
+    response(None, o, true)
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -2,8 +2,10 @@ use std::ops::Deref;
 use std::ops::Range;
 
 use crate::parsers::basic::*;
+use crate::parsers::bytes::*;
 use crate::parsers::execution::*;
 use crate::parsers::flow::*;
+use crate::parsers::grammar::*;
 use crate::parsers::monadic::*;
 use crate::parsers::parser::*;
 use crate::parsers::response::*;
@@ -34,6 +36,20 @@ pub struct DelimitedChar();
 
 impl Parser<char> for DelimitedChar {}
 
+// Literals are stored in a trie in order to match the longest one whatever their order
+
+struct TrieNode {
+    edges: Vec<(u8, usize)>,
+    value: Option<usize>,
+}
+
+pub struct Literals<A> {
+    nodes: Vec<TrieNode>,
+    values: Vec<A>,
+}
+
+impl<A> Parser<A> for Literals<A> {}
+
 //  -------------------------------------------------------------------------------------------------
 
 pub fn digit() -> Range<char> {
@@ -56,6 +72,89 @@ pub fn delimited_char() -> DelimitedChar {
     DelimitedChar()
 }
 
+// A literal given more than once is associated to its last value
+
+pub fn literals<A>(items: &[(&str, A)]) -> Literals<A>
+where
+    A: Clone,
+{
+    let mut nodes = vec![TrieNode {
+        edges: Vec::new(),
+        value: None,
+    }];
+    let mut values = Vec::with_capacity(items.len());
+
+    for (literal, value) in items {
+        let mut n = 0;
+
+        for b in literal.bytes() {
+            n = match nodes[n].edges.binary_search_by_key(&b, |e| e.0) {
+                Ok(i) => nodes[n].edges[i].1,
+                Err(i) => {
+                    let next = nodes.len();
+                    nodes[n].edges.insert(i, (b, next));
+                    nodes.push(TrieNode {
+                        edges: Vec::new(),
+                        value: None,
+                    });
+                    next
+                }
+            };
+        }
+
+        nodes[n].value = Some(values.len());
+        values.push(value.clone());
+    }
+
+    Literals { nodes, values }
+}
+
+impl<A> Literals<A> {
+    // Value index and end offset of the longest literal starting at o
+    fn longest(&self, s: &[u8], o: usize) -> Option<(usize, usize)> {
+        let mut node = &self.nodes[0];
+        let mut found = node.value.map(|v| (v, o));
+        let mut n = o;
+
+        while n < s.len() {
+            match node.edges.binary_search_by_key(&s[n], |e| e.0) {
+                Ok(i) => node = &self.nodes[node.edges[i].1],
+                Err(_) => break,
+            }
+
+            n += 1;
+
+            if let Some(v) = node.value {
+                found = Some((v, n));
+            }
+        }
+
+        found
+    }
+
+    // Literals stored in the trie in lexicographic order
+    fn entries(&self) -> Vec<String> {
+        let mut entries = Vec::new();
+        let mut pending = vec![(0, Vec::new())];
+
+        while let Some((n, prefix)) = pending.pop() {
+            let node = &self.nodes[n];
+
+            if node.value.is_some() {
+                entries.push(String::from_utf8_lossy(&prefix).to_string());
+            }
+
+            for (b, next) in node.edges.iter().rev() {
+                let mut literal = prefix.clone();
+                literal.push(*b);
+                pending.push((*next, literal));
+            }
+        }
+
+        entries
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // Parser execution
 // -------------------------------------------------------------------------------------------------
@@ -68,6 +167,14 @@ impl<'a> Executable<'a, u8> for u8 {
             _ => response(None, o, false),
         }
     }
+
+    fn describe(&self) -> Grammar {
+        if self.is_ascii() {
+            Grammar::Literal((*self as char).to_string())
+        } else {
+            Grammar::ByteClass(vec![(*self, *self)])
+        }
+    }
 }
 
 impl<'a> Parsable<'a, u8> for u8 {
@@ -90,6 +197,10 @@ impl<'a> Executable<'a, char> for char {
             _ => response(None, o, false),
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Literal(self.to_string())
+    }
 }
 
 impl<'a> Parsable<'a, char> for char {
@@ -119,6 +230,10 @@ impl<'a> Executable<'a, char> for Range<char> {
             _ => response(None, o, false),
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::CharClass(class_ranges(&[(self.start, self.end)]))
+    }
 }
 
 impl<'a> Parsable<'a, char> for Range<char> {
@@ -144,6 +259,14 @@ impl<'a> Executable<'a, String> for String {
     fn execute(&self, s: &'a [u8], o: usize) -> Response<String> {
         self.deref().execute(s, o).fmap(|s| s.to_string())
     }
+
+    fn nullable(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Literal(self.clone())
+    }
 }
 
 impl<'a> Parsable<'a, String> for String {
@@ -163,6 +286,14 @@ impl<'a> Executable<'a, &'a str> for &'a str {
             _ => response(None, r.o, r.c),
         }
     }
+
+    fn nullable(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Literal(self.to_string())
+    }
 }
 
 impl<'a> Parsable<'a, &'a str> for &'a str {
@@ -194,6 +325,10 @@ impl<'a> Executable<'a, &'a [u8]> for Float {
             _ => response(None, r.o, r.c),
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Terminal("float".to_string())
+    }
 }
 
 impl<'a> Parsable<'a, &'a [u8]> for Float {
@@ -218,16 +353,30 @@ impl<'a> Executable<'a, &'a [u8]> for DelimitedString {
             _ => response(None, r.o, r.c),
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Terminal("delimited_string".to_string())
+    }
 }
 
+const STRING_DELIMITERS: ByteSet = ByteSet::from_bytes(b"\"\\");
+
 impl<'a> Parsable<'a, &'a [u8]> for DelimitedString {
     fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
-        let c = '\\'
-            .then_right(any())
-            .or(any().satisfy(|b| *b as char != '"'));
-        let p = '"'.then(c.optrep()).then('"');
+        if o >= s.len() || s[o] != b'"' {
+            return response(None, o, false);
+        }
 
-        p.parse_only(s, o)
+        let mut n = o + 1;
+
+        loop {
+            match STRING_DELIMITERS.find(s, n) {
+                Some(m) if s[m] == b'"' => return response(Some(()), m + 1, true),
+                Some(m) if m + 1 < s.len() => n = m + 2,
+                Some(m) => return response(None, m, true),
+                None => return response(None, s.len(), true),
+            }
+        }
     }
 }
 
@@ -245,6 +394,10 @@ impl<'a> Executable<'a, char> for DelimitedChar {
 
         p.execute(s, o)
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Terminal("delimited_char".to_string())
+    }
 }
 
 impl<'a> Parsable<'a, char> for DelimitedChar {
@@ -252,3 +405,61 @@ impl<'a> Parsable<'a, char> for DelimitedChar {
         self.execute(s, o).fmap(|_| ())
     }
 }
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, A> Executable<'a, A> for Literals<A>
+where
+    A: Clone,
+{
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+        match self.longest(s, o) {
+            Some((v, n)) => response(Some(self.values[v].clone()), n, n > o),
+            None => response(None, o, false),
+        }
+    }
+
+    fn nullable(&self) -> bool {
+        self.nodes[0].value.is_some()
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::alt(self.entries().into_iter().map(Grammar::Literal).collect())
+    }
+}
+
+impl<'a, A> Parsable<'a, A> for Literals<A>
+where
+    A: Clone,
+{
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        match self.longest(s, o) {
+            Some((_, n)) => response(Some(()), n, n > o),
+            None => response(None, o, false),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Stateful parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl Stateless for u8 {}
+
+impl Stateless for char {}
+
+impl Stateless for Range<char> {}
+
+impl Stateless for String {}
+
+impl Stateless for &str {}
+
+impl Stateless for Float {}
+
+impl Stateless for DelimitedString {}
+
+impl Stateless for DelimitedChar {}
+
+impl<A> Stateless for Literals<A> {}
+
+// -------------------------------------------------------------------------------------------------
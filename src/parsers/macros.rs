@@ -21,14 +21,86 @@ macro_rules! lazy {
 
 // -------------------------------------------------------------------------------------------------
 
+// Items are sequenced from left to right and `~>` or `<~` discard the item on the side of `~`.
+// Kept items give the parsed value, a pair or a flat tuple, or the fields of a structure when
+// the sequence is given as `Name { field: (p) ~ ... }`. A final `>> f` maps this result.
+
 #[macro_export]
 macro_rules! seq {
-    (* ($($r:tt)+))          => { seq!($($r)+).optrep()       };
-    (($l:expr) <~ $($r:tt)+) => { $l.then_left(seq!($($r)+))  };
-    (($l:expr) ~> $($r:tt)+) => { $l.then_right(seq!($($r)+)) };
-    (($l:expr) ~  $($r:tt)+) => { $l.then(seq!($($r)+))       };
-    (($l:expr) >> $r:expr)   => { $l.fmap(Box::new($r))       };
-    (($l:expr))              => { $l                          };
+    (* ($($r:tt)+)) => {
+        seq!($($r)+).optrep()
+    };
+
+    // Items with the decision to keep or drop them
+    (_internal $s:tt $p:tt $pat:tt $out:tt $k:ident $n:ident : ($e:expr) $($r:tt)*) => {
+        seq!(_item $s $p $pat $out $k $n ($e) $($r)*)
+    };
+    (_internal $s:tt $p:tt $pat:tt $out:tt $k:ident ($e:expr) $($r:tt)*) => {
+        seq!(_item $s $p $pat $out $k _ ($e) $($r)*)
+    };
+    (_item $s:tt $p:tt $pat:tt $out:tt $k:ident $n:tt ($e:expr) ~> $($r:tt)+) => {
+        seq!(_add $s $p $pat $out drop $n ($e) keep $($r)+)
+    };
+    (_item $s:tt $p:tt $pat:tt $out:tt $k:ident $n:tt ($e:expr) <~ $($r:tt)+) => {
+        seq!(_add $s $p $pat $out $k $n ($e) drop $($r)+)
+    };
+    (_item $s:tt $p:tt $pat:tt $out:tt $k:ident $n:tt ($e:expr) ~ $($r:tt)+) => {
+        seq!(_add $s $p $pat $out $k $n ($e) keep $($r)+)
+    };
+    (_item $s:tt $p:tt $pat:tt $out:tt $k:ident $n:tt ($e:expr) $($r:tt)*) => {
+        seq!(_add $s $p $pat $out $k $n ($e) keep $($r)*)
+    };
+
+    // Sequence built with each kept value bound in the pattern
+    (_add $s:tt [] [] [] keep $n:tt ($e:expr) $($r:tt)*) => {
+        seq!(_next $s [$e] [v] [($n v)] $($r)*)
+    };
+    (_add $s:tt [] [] [] drop $n:tt ($e:expr) $($r:tt)*) => {
+        seq!(_next $s [$e] [_] [] $($r)*)
+    };
+    (_add $s:tt [$($p:tt)+] [_] [] keep $n:tt ($e:expr) $($r:tt)*) => {
+        seq!(_next $s [($($p)+).then_right($e)] [v] [($n v)] $($r)*)
+    };
+    (_add $s:tt [$($p:tt)+] $pat:tt $out:tt drop $n:tt ($e:expr) $($r:tt)*) => {
+        seq!(_next $s [($($p)+).then_left($e)] $pat $out $($r)*)
+    };
+    (_add $s:tt [$($p:tt)+] [$pat:tt] [$($out:tt)+] keep $n:tt ($e:expr) $($r:tt)*) => {
+        seq!(_next $s [($($p)+).then($e)] [($pat, v)] [$($out)+ ($n v)] $($r)*)
+    };
+
+    (_next $s:tt $p:tt $pat:tt $out:tt $k:ident >> $f:expr) => {
+        seq!(_end $s $p $pat $out).fmap($f)
+    };
+    (_next $s:tt $p:tt $pat:tt $out:tt $k:ident) => {
+        seq!(_end $s $p $pat $out)
+    };
+    (_next $s:tt $p:tt $pat:tt $out:tt $k:ident $($r:tt)+) => {
+        seq!(_internal $s $p $pat $out $k $($r)+)
+    };
+
+    // Result given by the kept values
+    (_end ($name:ident) [$($p:tt)+] [$pat:tt] [$(($n:ident $v:ident))+]) => {
+        ($($p)+).fmap(move |$pat| $name { $($n: $v),+ })
+    };
+    (_end ($name:ident) $($r:tt)*) => {
+        compile_error!("each kept item of a structure should be named")
+    };
+    (_end () [$($p:tt)+] $pat:tt [$(($n:tt $v:ident))?]) => {
+        $($p)+
+    };
+    (_end () [$($p:tt)+] $pat:tt [($n1:tt $v1:ident) ($n2:tt $v2:ident)]) => {
+        $($p)+
+    };
+    (_end () [$($p:tt)+] [$pat:tt] [$(($n:tt $v:ident))+]) => {
+        ($($p)+).fmap(move |$pat| ($($v),+))
+    };
+
+    ($name:ident { $($r:tt)+ }) => {
+        seq!(_internal ($name) [] [] [] keep $($r)+)
+    };
+    ($($r:tt)+) => {
+        seq!(_internal () [] [] [] keep $($r)+)
+    };
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -36,22 +108,46 @@ macro_rules! seq {
 #[macro_export]
 macro_rules! cases {
     (($l:expr) |  $($r:tt)+) => { $l.or(cases!($($r)+))       };
-    (($l:expr) >> $r:expr)   => { $l.fmap(Box::new($r))       };
+    (($l:expr) >> $r:expr)   => { $l.fmap($r)                 };
     ($l:expr)                => { $l                          };
 }
 
 // -------------------------------------------------------------------------------------------------
 
+// Steps bind patterns with `<-`, pure values with `let` and guards may give a failure label
+// with `else`. Since `<-` cannot follow a pattern fragment, the tokens of a pattern like
+// `Point { x, y }` or `mut x` are collected up to `<-` first. Guards borrow the bound value.
+
 #[macro_export]
 macro_rules! foreach {
-    (_internal $a:ident <- ($e:expr) if ($cond:expr) $($r:tt)+) => {
-        foreach!(_internal $a <- ($e.filter(move |&$a| $cond)) $($r)+)
+    (_internal let $p:pat = $e:expr; $($r:tt)+) => {{
+        let $p = $e;
+        foreach!(_internal $($r)+)
+    }};
+    (_internal yield $result:expr) => {
+        $crate::parsers::basic::returns($result)
+    };
+    (_internal [$($a:tt)+] <- ($e:expr) if ($cond:expr) else ($l:expr) $($r:tt)+) => {
+        foreach!(_internal [$($a)+] <- ($crate::parsers::label::label($l, foreach!(_internal guard [$($a)+] ($e) ($cond)))) $($r)+)
     };
-    (_internal $a:ident <- ($e:expr) yield $result:expr) => {
-        $e.map(move |$a| $result)
+    (_internal [$($a:tt)+] <- ($e:expr) if ($cond:expr) $($r:tt)+) => {
+        foreach!(_internal [$($a)+] <- (foreach!(_internal guard [$($a)+] ($e) ($cond))) $($r)+)
     };
-    (_internal $a:ident <- ($e:expr) $($r:tt)+) => {
-        $e.flat_map(move |$a| foreach!(_internal $($r)+))
+    (_internal guard [$($a:tt)+] ($e:expr) ($cond:expr)) => {
+        $e.filter(move |v| {
+            #[allow(unused_mut)]
+            let &($($a)+) = v;
+            $cond
+        })
+    };
+    (_internal [$($a:tt)+] <- ($e:expr) yield $result:expr) => {
+        $e.map(move |$($a)+| $result)
+    };
+    (_internal [$($a:tt)+] <- ($e:expr) $($r:tt)+) => {
+        $e.flat_map(move |$($a)+| foreach!(_internal $($r)+))
+    };
+    (_internal $a:tt <- $($r:tt)+) => {
+        foreach!(_internal [$a] <- $($r)+)
     };
     (_internal ($e:expr) yield $result:expr) => {
         $e.map(move |_| $result)
@@ -59,9 +155,67 @@ macro_rules! foreach {
     (_internal ($e:expr) $($r:tt)+) => {
         $e.then_right(foreach!(_internal $($r)+))
     };
+    (_internal pattern [$($a:tt)+] <- $($r:tt)+) => {
+        foreach!(_internal [$($a)+] <- $($r)+)
+    };
+    (_internal pattern [$($a:tt)+] $t:tt $($r:tt)+) => {
+        foreach!(_internal pattern [$($a)+ $t] $($r)+)
+    };
+    (_internal $t:tt $($r:tt)+) => {
+        foreach!(_internal pattern [$t] $($r)+)
+    };
     ($($r:tt)+) => {
         foreach!(_internal $($r)+)
     }
 }
 
 // -------------------------------------------------------------------------------------------------
+
+// Each rule of a grammar is referenced by calling its name in any rule body. The generated
+// structure gives one accessor per rule and each rule it gives keeps the whole grammar alive.
+
+#[macro_export]
+macro_rules! grammar {
+    (
+        $(#[$m:meta])*
+        $v:vis struct $name:ident<$a:lifetime> {
+            $($rule:ident : $t:ty = $e:expr;)+
+        }
+    ) => {
+        $(#[$m])*
+        $v struct $name<$a> {
+            $($rule: $crate::parsers::rule::Rule<$a, $t>,)+
+        }
+
+        impl<$a> $name<$a> {
+            #[allow(clippy::new_without_default)]
+            pub fn new() -> Self {
+                let rules = $name {
+                    $($rule: $crate::parsers::rule::rule(),)+
+                };
+
+                $(
+                    #[allow(unused_variables)]
+                    let $rule = || rules.$rule.clone();
+                )+
+
+                $(
+                    rules.$rule.define($crate::parsers::grammar::NamedOperation::named(
+                        $e,
+                        stringify!($rule),
+                    ));
+                )+
+
+                rules
+            }
+
+            $(
+                pub fn $rule(&self) -> &$crate::parsers::rule::Rule<$a, $t> {
+                    &self.$rule
+                }
+            )+
+        }
+    };
+}
+
+// -------------------------------------------------------------------------------------------------
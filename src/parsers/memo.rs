@@ -0,0 +1,154 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::parsers::context::*;
+use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+use crate::parsers::lint::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Memoization context
+// -------------------------------------------------------------------------------------------------
+
+// Responses are only recorded within a memoizing context and each context has its own
+// identifier. A table filled by another context is emptied before being used, so responses
+// never survive the parse they come from. Outside such a context a memoized parser is executed
+// as is.
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+struct MemoTable<A> {
+    scope: usize,
+    responses: HashMap<usize, Response<A>>,
+}
+
+pub struct Memo<E, A>(Rc<(E, RefCell<MemoTable<A>>)>)
+where
+    E: Parser<A>;
+
+impl<E, A> Parser<A> for Memo<E, A> where E: Parser<A> {}
+
+// Clones share the same table i.e. the same parser identity
+
+impl<E, A> Clone for Memo<E, A>
+where
+    E: Parser<A>,
+{
+    fn clone(&self) -> Self {
+        let Memo(node) = self;
+
+        Memo(node.clone())
+    }
+}
+
+impl<E, A> Memo<E, A>
+where
+    E: Parser<A>,
+{
+    pub fn clear(&self) {
+        let Memo(node) = self;
+
+        node.1.borrow_mut().responses.clear();
+    }
+}
+
+#[inline]
+pub fn memo<E, A>(p: E) -> Memo<E, A>
+where
+    E: Parser<A>,
+{
+    let table = MemoTable {
+        scope: 0,
+        responses: HashMap::new(),
+    };
+
+    Memo(Rc::new((p, RefCell::new(table))))
+}
+
+pub trait MemoOperation<E, A>
+where
+    E: Parser<A>,
+{
+    fn memo(self) -> Memo<E, A>;
+}
+
+impl<E, A> MemoOperation<E, A> for E
+where
+    E: Parser<A>,
+{
+    #[inline]
+    fn memo(self) -> Memo<E, A> {
+        memo(self)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A> Executable<'a, A> for Memo<E, A>
+where
+    E: Executable<'a, A> + Parser<A>,
+    A: Clone,
+{
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+        let Memo(node) = self;
+        let (p, table) = node.as_ref();
+        let scope = match state(|state| state.memo.get()) {
+            Some(scope) => scope,
+            None => return p.execute(s, o),
+        };
+
+        {
+            let mut table = table.borrow_mut();
+
+            if table.scope != scope {
+                table.scope = scope;
+                table.responses = HashMap::new();
+            }
+
+            if let Some(r) = table.responses.get(&o) {
+                return response(r.v.clone(), r.o, r.c);
+            }
+        }
+
+        // The table is not borrowed here since the parser can be reentrant
+        let r = p.execute(s, o);
+
+        table
+            .borrow_mut()
+            .responses
+            .insert(o, response(r.v.clone(), r.o, r.c));
+
+        r
+    }
+
+    fn nullable(&self) -> bool {
+        (self.0).0.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        (self.0).0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        (self.0).0.describe()
+    }
+}
+
+impl<'a, E, A> Parsable<'a, A> for Memo<E, A>
+where
+    E: Executable<'a, A> + Parser<A>,
+    A: Clone,
+{
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
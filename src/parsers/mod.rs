@@ -1,12 +1,29 @@
 pub mod basic;
+pub mod bytes;
+pub mod charset;
+pub mod context;
 pub mod core;
 pub mod data;
+pub mod derive;
+pub mod ebnf;
 pub mod execution;
 pub mod flow;
+pub mod generate;
+pub mod grammar;
+pub mod indentation;
+pub mod label;
+pub mod limits;
+pub mod lint;
 pub mod literal;
+pub mod memo;
 pub mod monadic;
 pub mod parser;
+pub mod peg;
+pub mod permutation;
+pub mod railroad;
 pub mod response;
+pub mod rule;
+pub mod state;
 
 #[macro_use]
 pub mod macros;
@@ -1,6 +1,8 @@
 use std::marker::PhantomData;
 
 use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+use crate::parsers::lint::*;
 use crate::parsers::parser::*;
 use crate::parsers::response::*;
 
@@ -8,23 +10,27 @@ use crate::parsers::response::*;
 // Parser type definition
 // -------------------------------------------------------------------------------------------------
 
-pub struct FMap<E, A, B>(E, Box<Fn(A) -> B>)
+// Boxed functions erase the function type and may borrow data living for 'b
+
+pub type FMapFn<'b, A, B> = Box<dyn Fn(A) -> B + 'b>;
+
+pub struct FMap<E, A, B, F = FMapFn<'static, A, B>>(E, F, PhantomData<(A, B)>)
 where
     E: Parser<A>;
 
-impl<E, A, B> Parser<B> for FMap<E, A, B> where E: Parser<A> {}
+impl<E, A, B, F> Parser<B> for FMap<E, A, B, F> where E: Parser<A> {}
 
 pub trait FMapOperation<E, A, B>
 where
     E: Parser<A>,
 {
-    fn fmap_box(self, f: Box<Fn(A) -> B>) -> FMap<E, A, B>;
-    fn fmap<F>(self, f: F) -> FMap<E, A, B>
+    fn fmap_box<'b>(self, f: FMapFn<'b, A, B>) -> FMap<E, A, B, FMapFn<'b, A, B>>;
+    fn fmap<F>(self, f: F) -> FMap<E, A, B, F>
     where
-        F: (Fn(A) -> B) + 'static;
-    fn map<F>(self, f: F) -> FMap<E, A, B>
+        F: Fn(A) -> B;
+    fn map<F>(self, f: F) -> FMap<E, A, B, F>
     where
-        F: (Fn(A) -> B) + 'static;
+        F: Fn(A) -> B;
 }
 
 impl<E, A, B> FMapOperation<E, A, B> for E
@@ -32,33 +38,35 @@ where
     E: Parser<A>,
 {
     #[inline]
-    fn fmap_box(self, f: Box<Fn(A) -> B>) -> FMap<E, A, B> {
-        FMap(self, f)
+    fn fmap_box<'b>(self, f: FMapFn<'b, A, B>) -> FMap<E, A, B, FMapFn<'b, A, B>> {
+        FMap(self, f, PhantomData)
     }
     #[inline]
-    fn fmap<F>(self, f: F) -> FMap<E, A, B>
+    fn fmap<F>(self, f: F) -> FMap<E, A, B, F>
     where
-        F: (Fn(A) -> B) + 'static,
+        F: Fn(A) -> B,
     {
-        self.fmap_box(Box::new(f))
+        FMap(self, f, PhantomData)
     }
     #[inline]
-    fn map<F>(self, f: F) -> FMap<E, A, B>
+    fn map<F>(self, f: F) -> FMap<E, A, B, F>
     where
-        F: (Fn(A) -> B) + 'static,
+        F: Fn(A) -> B,
     {
-        self.fmap_box(Box::new(f))
+        FMap(self, f, PhantomData)
     }
 }
 
 // -------------------------------------------------------------------------------------------------
 
-pub struct Bind<E, A, R, B>(E, Box<Fn(A) -> R>, PhantomData<B>)
+pub type BindFn<'b, A, R> = Box<dyn Fn(A) -> R + 'b>;
+
+pub struct Bind<E, A, R, B, F = BindFn<'static, A, R>>(E, F, PhantomData<(A, R, B)>)
 where
     E: Parser<A>,
     R: Parser<B>;
 
-impl<E, A, R, B> Parser<B> for Bind<E, A, R, B>
+impl<E, A, R, B, F> Parser<B> for Bind<E, A, R, B, F>
 where
     E: Parser<A>,
     R: Parser<B>,
@@ -70,13 +78,13 @@ where
     E: Parser<A>,
     R: Parser<B>,
 {
-    fn bind_box(self, f: Box<Fn(A) -> R>) -> Bind<E, A, R, B>;
-    fn bind<F>(self, f: F) -> Bind<E, A, R, B>
+    fn bind_box<'b>(self, f: BindFn<'b, A, R>) -> Bind<E, A, R, B, BindFn<'b, A, R>>;
+    fn bind<F>(self, f: F) -> Bind<E, A, R, B, F>
     where
-        F: (Fn(A) -> R) + 'static;
-    fn flat_map<F>(self, f: F) -> Bind<E, A, R, B>
+        F: Fn(A) -> R;
+    fn flat_map<F>(self, f: F) -> Bind<E, A, R, B, F>
     where
-        F: (Fn(A) -> R) + 'static;
+        F: Fn(A) -> R;
 }
 
 impl<E, A, R, B> BindOperation<E, A, R, B> for E
@@ -85,20 +93,20 @@ where
     R: Parser<B>,
 {
     #[inline]
-    fn bind_box(self, f: Box<Fn(A) -> R>) -> Bind<E, A, R, B> {
+    fn bind_box<'b>(self, f: BindFn<'b, A, R>) -> Bind<E, A, R, B, BindFn<'b, A, R>> {
         Bind(self, f, PhantomData)
     }
     #[inline]
-    fn bind<F>(self, f: F) -> Bind<E, A, R, B>
+    fn bind<F>(self, f: F) -> Bind<E, A, R, B, F>
     where
-        F: (Fn(A) -> R) + 'static,
+        F: Fn(A) -> R,
     {
-        Bind(self, Box::new(f), PhantomData)
+        Bind(self, f, PhantomData)
     }
     #[inline]
-    fn flat_map<F>(self, f: F) -> Bind<E, A, R, B>
+    fn flat_map<F>(self, f: F) -> Bind<E, A, R, B, F>
     where
-        F: (Fn(A) -> R) + 'static,
+        F: Fn(A) -> R,
     {
         self.bind(f)
     }
@@ -108,12 +116,13 @@ where
 // Parser execution
 // -------------------------------------------------------------------------------------------------
 
-impl<'a, E, A, B> Executable<'a, B> for FMap<E, A, B>
+impl<'a, E, A, B, F> Executable<'a, B> for FMap<E, A, B, F>
 where
     E: Executable<'a, A> + Parser<A>,
+    F: Fn(A) -> B,
 {
     fn execute(&self, s: &'a [u8], o: usize) -> Response<B> {
-        let FMap(parser, function) = self;
+        let FMap(parser, function, _) = self;
         let result = parser.execute(s, o);
 
         match result.v {
@@ -121,14 +130,26 @@ where
             _ => response(None, result.o, result.c),
         }
     }
+
+    fn nullable(&self) -> bool {
+        self.0.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        self.0.describe()
+    }
 }
 
-impl<'a, E, A, B> Parsable<'a, B> for FMap<E, A, B>
+impl<'a, E, A, B, F> Parsable<'a, B> for FMap<E, A, B, F>
 where
     E: Parsable<'a, A> + Parser<A>,
 {
     fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
-        let FMap(parser, _) = self;
+        let FMap(parser, _, _) = self;
         let result = parser.parse_only(s, o);
 
         match result.v {
@@ -140,10 +161,11 @@ where
 
 // -------------------------------------------------------------------------------------------------
 
-impl<'a, E, A, R, B> Executable<'a, B> for Bind<E, A, R, B>
+impl<'a, E, A, R, B, F> Executable<'a, B> for Bind<E, A, R, B, F>
 where
     E: Executable<'a, A> + Parser<A>,
     R: Executable<'a, B> + Parser<B>,
+    F: Fn(A) -> R,
 {
     fn execute(&self, s: &'a [u8], o: usize) -> Response<B> {
         let Bind(p, f, _) = self;
@@ -161,6 +183,68 @@ where
             _ => response(None, r1.o, r1.c),
         }
     }
+
+    // The bound parser is unknown and may not consume input
+
+    fn nullable(&self) -> bool {
+        self.0.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    // The parser depends on the parsed value and cannot be described
+
+    fn describe(&self) -> Grammar {
+        Grammar::Dynamic
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Stateful parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, B, F, S> StatefulExecutable<'a, B, S> for FMap<E, A, B, F>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    F: Fn(A) -> B,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<B> {
+        let FMap(parser, function, _) = self;
+        let result = parser.execute_with(s, o, state);
+
+        match result.v {
+            Some(value) => response(Some(function(value)), result.o, result.c),
+            _ => response(None, result.o, result.c),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, R, B, F, S> StatefulExecutable<'a, B, S> for Bind<E, A, R, B, F>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    R: StatefulExecutable<'a, B, S> + Parser<B>,
+    F: Fn(A) -> R,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<B> {
+        let Bind(p, f, _) = self;
+        let r1 = p.execute_with(s, o, state);
+
+        match r1.v {
+            Some(a1) => {
+                let r2 = f(a1).execute_with(s, r1.o, state);
+
+                match r2.v {
+                    Some(a2) => response(Some(a2), r2.o, r1.c || r2.c),
+                    _ => response(None, r2.o, r1.c || r2.c),
+                }
+            }
+            _ => response(None, r1.o, r1.c),
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -0,0 +1,542 @@
+use std::collections::HashMap;
+
+use crate::parsers::basic::*;
+use crate::parsers::charset::*;
+use crate::parsers::core::*;
+use crate::parsers::execution::*;
+use crate::parsers::flow::*;
+use crate::parsers::grammar::*;
+use crate::parsers::lint::*;
+use crate::parsers::monadic::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+use crate::parsers::rule::*;
+
+// -------------------------------------------------------------------------------------------------
+// Parse tree definition
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tree {
+    // Text matched by a literal, a character class or `.`
+    Leaf(String),
+    // Rule name and the trees produced by its definition
+    Node(String, Vec<Tree>),
+}
+
+impl Tree {
+    pub fn text(&self) -> String {
+        match self {
+            Tree::Leaf(text) => text.clone(),
+            Tree::Node(_, children) => children.iter().map(Tree::text).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadError {
+    // Offset in the grammar text
+    Syntax(usize),
+    Undefined(String),
+    Duplicate(String),
+    Class(String),
+    LeftRecursion(String),
+}
+
+// -------------------------------------------------------------------------------------------------
+// Grammar loading
+// -------------------------------------------------------------------------------------------------
+
+// A grammar is a list of definitions `name <- expression` where `::=` can also be used.
+// Expressions are PEG ones:
+//
+//   e1 e2        sequence, items can also be separated by `,`
+//   e1 / e2      ordered choice, `|` can also be used
+//   e? e* e+     option, repetitions and `{ e }` for zero or more
+//   &e !e        positive and negative lookahead
+//   "s" 's'      literal with `\n`, `\r` and `\t` escapes
+//   [a-z]        character class using the `char_class` syntax
+//   .            any character
+//   ^            cut: the following alternatives are not tried once it is reached
+//
+// ISO EBNF definitions `name = expression ;` use `[ e ]` for an option and `? [a-z] ?` for a
+// character class, so `?` is not a suffix there. Comments start with `#` or are delimited by
+// `(* *)` or `/* */`. The first definition is the start rule and each rule produces a node with
+// the trees of its definition.
+
+pub fn load<'a>(text: &str) -> Result<Parsec<'a, Tree>, LoadError> {
+    let definitions = meta()
+        .execute(text.as_bytes(), 0)
+        .fold(|v, _, _| Ok(v), |o, _| Err(LoadError::Syntax(o)))?;
+
+    let mut rules = HashMap::new();
+    let mut owners = Vec::with_capacity(definitions.len());
+
+    for (name, _) in &definitions {
+        if rules.contains_key(name) {
+            return Err(LoadError::Duplicate(name.clone()));
+        }
+
+        let owner = rule();
+        rules.insert(name.clone(), owner.clone());
+        owners.push(owner);
+    }
+
+    for ((name, expr), owner) in definitions.iter().zip(&owners) {
+        let node = name.clone();
+
+        owner.define(
+            compile(expr, &rules)?
+                .fmap(move |children| Tree::Node(node.clone(), children))
+                .named(name),
+        );
+    }
+
+    if let Some(name) = left_recursion(&definitions, &rules) {
+        return Err(LoadError::LeftRecursion(name));
+    }
+
+    Ok(Parsec(Box::new(Rules(owners))))
+}
+
+// A rule calling itself, directly or not, before consuming input would loop forever. Rules
+// called at the current offset are found using the nullable analysis of the compiled rules.
+
+fn left_recursion(
+    definitions: &[(String, Expr)],
+    rules: &HashMap<String, Rule<'_, Tree>>,
+) -> Option<String> {
+    let calls: HashMap<&String, Vec<String>> = definitions
+        .iter()
+        .map(|(name, expr)| {
+            let mut called = Vec::new();
+            expr.leftmost(rules, &mut called);
+            (name, called)
+        })
+        .collect();
+
+    definitions
+        .iter()
+        .map(|(name, _)| name)
+        .find(|name| {
+            let mut visited = vec![];
+            let mut pending = calls[name].clone();
+
+            while let Some(called) = pending.pop() {
+                if &called == *name {
+                    return true;
+                }
+                if !visited.contains(&called) {
+                    pending.extend(calls[&called].iter().cloned());
+                    visited.push(called);
+                }
+            }
+
+            false
+        })
+        .cloned()
+}
+
+// -------------------------------------------------------------------------------------------------
+// Grammar definition
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Literal(String),
+    Class(String),
+    Any,
+    Rule(String),
+    Seq(Vec<Expr>),
+    Alt(Vec<Expr>),
+    Opt(Box<Expr>),
+    OptRep(Box<Expr>),
+    Rep(Box<Expr>),
+    And(Box<Expr>),
+    Not(Box<Expr>),
+    Cut,
+}
+
+impl Expr {
+    fn seq(mut items: Vec<Expr>) -> Expr {
+        if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::Seq(items)
+        }
+    }
+
+    fn alt(mut items: Vec<Expr>) -> Expr {
+        if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::Alt(items)
+        }
+    }
+
+    fn nullable(&self, rules: &HashMap<String, Rule<'_, Tree>>) -> bool {
+        match self {
+            Expr::Literal(s) => s.is_empty(),
+            Expr::Class(_) | Expr::Any => false,
+            Expr::Rule(name) => rules[name].nullable(),
+            Expr::Seq(items) => items.iter().all(|e| e.nullable(rules)),
+            Expr::Alt(items) => items.iter().any(|e| e.nullable(rules)),
+            Expr::Rep(e) => e.nullable(rules),
+            Expr::Opt(_) | Expr::OptRep(_) | Expr::And(_) | Expr::Not(_) | Expr::Cut => true,
+        }
+    }
+
+    // Rules called before consuming input
+    fn leftmost(&self, rules: &HashMap<String, Rule<'_, Tree>>, called: &mut Vec<String>) {
+        match self {
+            Expr::Rule(name) => called.push(name.clone()),
+            Expr::Seq(items) => {
+                for e in items {
+                    e.leftmost(rules, called);
+                    if !e.nullable(rules) {
+                        break;
+                    }
+                }
+            }
+            Expr::Alt(items) => items.iter().for_each(|e| e.leftmost(rules, called)),
+            Expr::Opt(e) | Expr::OptRep(e) | Expr::Rep(e) | Expr::And(e) | Expr::Not(e) => {
+                e.leftmost(rules, called)
+            }
+            Expr::Literal(_) | Expr::Class(_) | Expr::Any | Expr::Cut => (),
+        }
+    }
+
+    fn cuts(&self) -> bool {
+        match self {
+            Expr::Cut => true,
+            Expr::Seq(items) => items.iter().any(|e| matches!(e, Expr::Cut)),
+            _ => false,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Grammar parser
+// -------------------------------------------------------------------------------------------------
+
+fn spacing<'b>() -> Parsec<'b, ()> {
+    let blank = || skip(" \t\r\n".to_string());
+    let comment = choice((
+        Parsec(Box::new('#'.then(take_while(|b| *b != b'\n')).fmap(|_| ()))),
+        Parsec(Box::new(
+            "(*".then(take_until("*)")).then("*)").fmap(|_| ()),
+        )),
+        Parsec(Box::new(
+            "/*".then(take_until("*/")).then("*/").fmap(|_| ()),
+        )),
+    ));
+
+    Parsec(Box::new(
+        blank().then(comment.then(blank()).optrep()).fmap(|_| ()),
+    ))
+}
+
+fn lexeme<'b, E, A>(p: E) -> Parsec<'b, A>
+where
+    E: Executable<'b, A> + 'b,
+    A: 'b,
+{
+    Parsec(Box::new(p.then_left(spacing())))
+}
+
+fn not<'b, E, A>(p: E) -> Parsec<'b, ()>
+where
+    E: Executable<'b, A> + 'b,
+    A: 'b,
+{
+    Parsec(Box::new(
+        lookahead(do_try(p))
+            .opt()
+            .filter(Option::is_none)
+            .fmap(|_| ()),
+    ))
+}
+
+fn arrow<'b>() -> Parsec<'b, ()> {
+    lexeme(choice(("<-", "::=", "=")).fmap(|_| ()))
+}
+
+fn identifier<'b>() -> Parsec<'b, String> {
+    let first = char_class("a-zA-Z_");
+    let rest = take_while(|b| b.is_ascii_alphanumeric() || *b == b'_');
+
+    lexeme(
+        first
+            .then(rest)
+            .fmap(|(c, rest)| format!("{}{}", c, String::from_utf8_lossy(&rest))),
+    )
+}
+
+fn quoted<'b>(quote: u8) -> Parsec<'b, String> {
+    let escaped = '\\'.then_right(any()).fmap(|b| match b {
+        b'n' => b'\n',
+        b'r' => b'\r',
+        b't' => b'\t',
+        b => b,
+    });
+    let plain = take_one(move |b| *b != quote && *b != b'\\');
+
+    lexeme(
+        quote
+            .then_right(escaped.or(plain).optrep())
+            .then_left(quote)
+            .fmap(|v| String::from_utf8_lossy(&v).into_owned()),
+    )
+}
+
+// Escapes are kept in the class specification except for control characters
+fn class<'b>() -> Parsec<'b, String> {
+    let escaped = '\\'.then_right(any()).fmap(|b| match b {
+        b'n' => vec![b'\n'],
+        b'r' => vec![b'\r'],
+        b't' => vec![b'\t'],
+        b => vec![b'\\', b],
+    });
+    let plain = take_one(|b| *b != b']' && *b != b'\\').fmap(|b| vec![b]);
+
+    lexeme(
+        '['.then_right(escaped.or(plain).optrep())
+            .then_left(']')
+            .fmap(|v| String::from_utf8_lossy(&v.concat()).into_owned()),
+    )
+}
+
+fn meta<'b>() -> Parsec<'b, Vec<(String, Expr)>> {
+    let peg = rule();
+    let iso = rule();
+
+    let sequence = |expression: &Rule<'b, Expr>, iso: bool| {
+        let mut primaries = vec![
+            Parsec(Box::new(
+                identifier().then_left(not(arrow())).fmap(Expr::Rule),
+            )),
+            Parsec(Box::new(
+                lexeme('(')
+                    .then_right(expression.clone())
+                    .then_left(lexeme(')')),
+            )),
+            Parsec(Box::new(
+                lexeme('{')
+                    .then_right(expression.clone())
+                    .then_left(lexeme('}'))
+                    .fmap(|e| Expr::OptRep(Box::new(e))),
+            )),
+            Parsec(Box::new(quoted(b'"').or(quoted(b'\'')).fmap(Expr::Literal))),
+        ];
+
+        if iso {
+            primaries.push(Parsec(Box::new(
+                lexeme('[')
+                    .then_right(expression.clone())
+                    .then_left(lexeme(']'))
+                    .fmap(|e| Expr::Opt(Box::new(e))),
+            )));
+            primaries.push(Parsec(Box::new(
+                lexeme('?')
+                    .then_right(class())
+                    .then_left(lexeme('?'))
+                    .fmap(Expr::Class),
+            )));
+        } else {
+            primaries.push(Parsec(Box::new(class().fmap(Expr::Class))));
+        }
+
+        primaries.push(Parsec(Box::new(lexeme('.').fmap(|_| Expr::Any))));
+        primaries.push(Parsec(Box::new(lexeme('^').fmap(|_| Expr::Cut))));
+
+        let suffixes: &[u8] = if iso { b"*+" } else { b"?*+" };
+        let suffix = choice_vec(primaries)
+            .then(lexeme(one_of(suffixes)).opt())
+            .fmap(|(e, op)| match op {
+                Some(b'?') => Expr::Opt(Box::new(e)),
+                Some(b'*') => Expr::OptRep(Box::new(e)),
+                Some(b'+') => Expr::Rep(Box::new(e)),
+                _ => e,
+            });
+        let prefix = lexeme(one_of(b"&!"))
+            .opt()
+            .then(suffix)
+            .fmap(|(op, e)| match op {
+                Some(b'&') => Expr::And(Box::new(e)),
+                Some(b'!') => Expr::Not(Box::new(e)),
+                _ => e,
+            });
+
+        prefix.then_left(lexeme(',').opt()).optrep().fmap(Expr::seq)
+    };
+    let expression = |expression: &Rule<'b, Expr>, iso: bool| {
+        sequence(expression, iso)
+            .then(
+                lexeme(one_of(b"/|"))
+                    .then_right(sequence(expression, iso))
+                    .optrep(),
+            )
+            .fmap(|(first, mut rest)| {
+                rest.insert(0, first);
+                Expr::alt(rest)
+            })
+    };
+
+    peg.define(expression(&peg, false));
+    iso.define(expression(&iso, true));
+
+    let definition = identifier()
+        .then(
+            lexeme('=')
+                .then_right(iso)
+                .or(lexeme(choice(("<-", "::="))).then_right(peg)),
+        )
+        .then_left(lexeme(';').opt());
+
+    Parsec(Box::new(
+        spacing().then_right(definition.rep()).then_left(eos()),
+    ))
+}
+
+// -------------------------------------------------------------------------------------------------
+// Grammar compilation
+// -------------------------------------------------------------------------------------------------
+
+type Trees<'a> = Parsec<'a, Vec<Tree>>;
+
+fn concat((mut a, b): (Vec<Tree>, Vec<Tree>)) -> Vec<Tree> {
+    a.extend(b);
+    a
+}
+
+// PEG alternatives, options and repetitions backtrack unless a cut is reached
+
+fn compile<'a>(
+    expr: &Expr,
+    rules: &HashMap<String, Rule<'a, Tree>>,
+) -> Result<Trees<'a>, LoadError> {
+    let leaf = |s: String| vec![Tree::Leaf(s)];
+
+    Ok(match expr {
+        Expr::Literal(s) => Parsec(Box::new(s.clone().fmap(leaf))),
+        Expr::Class(spec) => match try_char_class(spec) {
+            Ok(class) => Parsec(Box::new(class.fmap(move |c| leaf(c.to_string())))),
+            Err(_) => return Err(LoadError::Class(spec.clone())),
+        },
+        Expr::Any => Parsec(Box::new(none_of("").fmap(move |c| leaf(c.to_string())))),
+        Expr::Rule(name) => match rules.get(name) {
+            Some(r) => Parsec(Box::new(r.clone().fmap(|t| vec![t]))),
+            None => return Err(LoadError::Undefined(name.clone())),
+        },
+        Expr::Seq(items) => sequence(items, rules)?,
+        Expr::Alt(items) => {
+            let mut alternatives = Vec::with_capacity(items.len());
+
+            for e in items {
+                let p = compile(e, rules)?;
+                alternatives.push(if e.cuts() {
+                    p
+                } else {
+                    Parsec(Box::new(do_try(p)))
+                });
+            }
+
+            Parsec(Box::new(choice_vec(alternatives)))
+        }
+        Expr::Opt(e) => Parsec(Box::new(
+            do_try(compile(e, rules)?)
+                .opt()
+                .fmap(Option::unwrap_or_default),
+        )),
+        Expr::OptRep(e) => Parsec(Box::new(
+            do_try(compile(e, rules)?).optrep().fmap(|v| v.concat()),
+        )),
+        Expr::Rep(e) => Parsec(Box::new(
+            do_try(compile(e, rules)?).rep().fmap(|v| v.concat()),
+        )),
+        Expr::And(e) => Parsec(Box::new(
+            lookahead(do_try(compile(e, rules)?)).fmap(|_| vec![]),
+        )),
+        Expr::Not(e) => Parsec(Box::new(not(compile(e, rules)?).fmap(|_| vec![]))),
+        Expr::Cut => Parsec(Box::new(Cut())),
+    })
+}
+
+fn sequence<'a>(
+    items: &[Expr],
+    rules: &HashMap<String, Rule<'a, Tree>>,
+) -> Result<Trees<'a>, LoadError> {
+    if let Some(n) = items.iter().rposition(|e| matches!(e, Expr::Cut)) {
+        let before = sequence(&items[..n], rules)?;
+        let after = sequence(&items[n + 1..], rules)?;
+
+        return Ok(Parsec(Box::new(
+            do_try(before).then_left(Cut()).then(after).fmap(concat),
+        )));
+    }
+
+    let mut p = Parsec(Box::new(returns(vec![])));
+
+    for e in items {
+        p = Parsec(Box::new(p.then(compile(e, rules)?).fmap(concat)));
+    }
+
+    Ok(p)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+// The cut succeeds without consuming input but is considered as consuming. The alternative
+// is then committed like any other alternative which consumed input.
+
+struct Cut();
+
+impl Parser<Vec<Tree>> for Cut {}
+
+// Rules are owned by the loaded parser, the first one being the start rule
+
+struct Rules<'a>(Vec<Rule<'a, Tree>>);
+
+impl<'a> Parser<Tree> for Rules<'a> {}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a> Executable<'a, Vec<Tree>> for Cut {
+    fn execute(&self, _: &'a [u8], o: usize) -> Response<Vec<Tree>> {
+        response(Some(vec![]), o, true)
+    }
+
+    fn nullable(&self) -> bool {
+        true
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Empty
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a> Executable<'a, Tree> for Rules<'a> {
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<Tree> {
+        self.0[0].execute(s, o)
+    }
+
+    fn nullable(&self) -> bool {
+        self.0[0].nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0[0].check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        self.0[0].describe()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
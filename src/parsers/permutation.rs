@@ -0,0 +1,355 @@
+use core::marker::PhantomData;
+
+use crate::parsers::execution::*;
+use crate::parsers::label::*;
+use crate::parsers::lint::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+pub struct Required<E, A>(pub E, PhantomData<A>)
+where
+    E: Parser<A>;
+
+impl<E, A> Parser<A> for Required<E, A> where E: Parser<A> {}
+
+#[inline]
+pub fn required<E, A>(p: E) -> Required<E, A>
+where
+    E: Parser<A>,
+{
+    Required(p, PhantomData)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub struct Optional<E, A>(pub E, PhantomData<A>)
+where
+    E: Parser<A>;
+
+impl<E, A> Parser<Option<A>> for Optional<E, A> where E: Parser<A> {}
+
+#[inline]
+pub fn optional<E, A>(p: E) -> Optional<E, A>
+where
+    E: Parser<A>,
+{
+    Optional(p, PhantomData)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// A permutation rejects an item found again after it since each item is given at most once. A
+// lenient permutation stops before such item instead, leaving it to the following parser.
+
+pub struct Permutation<T, A>(pub T, pub bool, PhantomData<A>);
+
+#[inline]
+pub fn permutation<T, A>(p: T) -> Permutation<T, A>
+where
+    Permutation<T, A>: Parser<A>,
+{
+    Permutation(p, true, PhantomData)
+}
+
+#[inline]
+pub fn lenient_permutation<T, A>(p: T) -> Permutation<T, A>
+where
+    Permutation<T, A>: Parser<A>,
+{
+    Permutation(p, false, PhantomData)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Permutation item definition
+// -------------------------------------------------------------------------------------------------
+
+pub trait PermutationItem<'a, A>
+where
+    Self: Parser<A>,
+{
+    type Item;
+
+    fn execute_item(&self, s: &'a [u8], o: usize) -> Response<Self::Item>;
+
+    fn complete(&self, v: Option<Self::Item>) -> Option<A>;
+
+    fn nullable_item(&self) -> bool;
+
+    fn check_item(&self, warnings: &mut Vec<Warning>);
+}
+
+impl<'a, E, A> PermutationItem<'a, A> for Required<E, A>
+where
+    E: Executable<'a, A> + Parser<A>,
+{
+    type Item = A;
+
+    #[inline]
+    fn execute_item(&self, s: &'a [u8], o: usize) -> Response<A> {
+        let Required(p, _) = self;
+
+        p.execute(s, o)
+    }
+
+    #[inline]
+    fn complete(&self, v: Option<A>) -> Option<A> {
+        v
+    }
+
+    fn nullable_item(&self) -> bool {
+        self.0.nullable()
+    }
+
+    fn check_item(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+}
+
+impl<'a, E, A> PermutationItem<'a, Option<A>> for Optional<E, A>
+where
+    E: Executable<'a, A> + Parser<A>,
+{
+    type Item = A;
+
+    #[inline]
+    fn execute_item(&self, s: &'a [u8], o: usize) -> Response<A> {
+        let Optional(p, _) = self;
+
+        p.execute(s, o)
+    }
+
+    #[inline]
+    fn complete(&self, v: Option<A>) -> Option<Option<A>> {
+        Some(v)
+    }
+
+    fn nullable_item(&self) -> bool {
+        true
+    }
+
+    fn check_item(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub trait StatefulPermutationItem<'a, A, S>
+where
+    Self: Parser<A>,
+{
+    type Item;
+
+    fn execute_item_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<Self::Item>;
+
+    fn complete_with(&self, v: Option<Self::Item>) -> Option<A>;
+}
+
+impl<'a, E, A, S> StatefulPermutationItem<'a, A, S> for Required<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+{
+    type Item = A;
+
+    #[inline]
+    fn execute_item_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Required(p, _) = self;
+
+        p.execute_with(s, o, state)
+    }
+
+    #[inline]
+    fn complete_with(&self, v: Option<A>) -> Option<A> {
+        v
+    }
+}
+
+impl<'a, E, A, S> StatefulPermutationItem<'a, Option<A>, S> for Optional<E, A>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+{
+    type Item = A;
+
+    #[inline]
+    fn execute_item_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let Optional(p, _) = self;
+
+        p.execute_with(s, o, state)
+    }
+
+    #[inline]
+    fn complete_with(&self, v: Option<A>) -> Option<Option<A>> {
+        Some(v)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+macro_rules! permutation_parsers {
+    ($($t:ident $a:ident $p:ident $v:ident),+) => {
+        impl<$($t, $a),+> Parser<($($a,)+)> for Permutation<($($t,)+), ($($a,)+)>
+        where
+            $($t: Parser<$a>),+
+        {
+        }
+
+        impl<'a, $($t, $a),+> Executable<'a, ($($a,)+)> for Permutation<($($t,)+), ($($a,)+)>
+        where
+            $($t: PermutationItem<'a, $a>),+
+        {
+            fn execute(&self, s: &'a [u8], o: usize) -> Response<($($a,)+)> {
+                let Permutation(($($p,)+), strict, _) = self;
+                let mut offset = o;
+                let mut consumed = false;
+                $(let mut $v = None;)+
+
+                loop {
+                    let mut found = false;
+
+                    $(
+                        if !found && $v.is_none() {
+                            let r = $p.execute_item(s, offset);
+                            match r.v {
+                                Some(v) => {
+                                    $v = Some(v);
+                                    offset = r.o;
+                                    consumed = consumed || r.c;
+                                    found = true;
+                                }
+                                None if r.c => return response(None, r.o, true),
+                                None => (),
+                            }
+                        }
+                    )+
+
+                    if !found {
+                        break;
+                    }
+                }
+
+                // An item already parsed occurring a second time is a duplicate
+                $(
+                    if *strict && $v.is_some() && $p.execute_item(s, offset).v.is_some() {
+                        record(offset, "duplicate item");
+                        return response(None, offset, true);
+                    }
+                )+
+
+                $(
+                    let $v = match $p.complete($v) {
+                        Some(v) => v,
+                        None => {
+                            record(offset, "missing item");
+                            return response(None, offset, consumed);
+                        }
+                    };
+                )+
+
+                response(Some(($($v,)+)), offset, consumed)
+            }
+
+            fn nullable(&self) -> bool {
+                let Permutation(($($p,)+), _, _) = self;
+
+                true $(&& $p.nullable_item())+
+            }
+
+            fn check(&self, warnings: &mut Vec<Warning>) {
+                let Permutation(($($p,)+), _, _) = self;
+
+                $($p.check_item(warnings);)+
+            }
+        }
+
+        // The state is restored when an item fails without consuming input and after the
+        // duplicate item check
+
+        impl<'a, S, $($t, $a),+> StatefulExecutable<'a, ($($a,)+), S> for Permutation<($($t,)+), ($($a,)+)>
+        where
+            $($t: StatefulPermutationItem<'a, $a, S>),+,
+            S: Clone,
+        {
+            fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<($($a,)+)> {
+                let Permutation(($($p,)+), strict, _) = self;
+                let mut offset = o;
+                let mut consumed = false;
+                $(let mut $v = None;)+
+
+                loop {
+                    let mut found = false;
+
+                    $(
+                        if !found && $v.is_none() {
+                            let saved = state.clone();
+                            let r = $p.execute_item_with(s, offset, state);
+                            match r.v {
+                                Some(v) => {
+                                    $v = Some(v);
+                                    offset = r.o;
+                                    consumed = consumed || r.c;
+                                    found = true;
+                                }
+                                None if r.c => return response(None, r.o, true),
+                                None => *state = saved,
+                            }
+                        }
+                    )+
+
+                    if !found {
+                        break;
+                    }
+                }
+
+                $(
+                    if *strict && $v.is_some() {
+                        let saved = state.clone();
+                        let duplicate = $p.execute_item_with(s, offset, state).v.is_some();
+                        *state = saved;
+
+                        if duplicate {
+                            record(offset, "duplicate item");
+                            return response(None, offset, true);
+                        }
+                    }
+                )+
+
+                $(
+                    let $v = match $p.complete_with($v) {
+                        Some(v) => v,
+                        None => {
+                            record(offset, "missing item");
+                            return response(None, offset, consumed);
+                        }
+                    };
+                )+
+
+                response(Some(($($v,)+)), offset, consumed)
+            }
+        }
+
+        impl<'a, $($t, $a),+> Parsable<'a, ($($a,)+)> for Permutation<($($t,)+), ($($a,)+)>
+        where
+            $($t: PermutationItem<'a, $a>),+
+        {
+            fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+                self.execute(s, o).fmap(|_| ())
+            }
+        }
+    };
+}
+
+permutation_parsers!(P1 A1 p1 a1, P2 A2 p2 a2);
+permutation_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3);
+permutation_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4);
+permutation_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5);
+permutation_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6);
+permutation_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6, P7 A7 p7 a7);
+permutation_parsers!(P1 A1 p1 a1, P2 A2 p2 a2, P3 A3 p3 a3, P4 A4 p4 a4, P5 A5 p5 a5, P6 A6 p6 a6, P7 A7 p7 a7, P8 A8 p8 a8);
+
+// -------------------------------------------------------------------------------------------------
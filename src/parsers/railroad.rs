@@ -0,0 +1,317 @@
+use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+
+// -------------------------------------------------------------------------------------------------
+// Railroad diagrams
+// -------------------------------------------------------------------------------------------------
+
+// Each named rule is drawn as a standalone SVG document. Literals are drawn in rounded boxes,
+// rule references in boxes and other terminals like character classes in dashed boxes.
+
+pub fn railroad<'a, E, A>(p: &E) -> Vec<(String, String)>
+where
+    E: Executable<'a, A>,
+{
+    to_railroad(&p.describe())
+}
+
+pub fn to_railroad(grammar: &Grammar) -> Vec<(String, String)> {
+    grammar
+        .rules("grammar")
+        .iter()
+        .map(|(name, g)| (name.clone(), diagram(g)))
+        .collect()
+}
+
+pub fn diagram(grammar: &Grammar) -> String {
+    let node = Node::from(grammar);
+    let width = node.width + 2 * (PADDING + STUB);
+    let height = node.up + node.down + 2 * PADDING;
+    let x = PADDING;
+    let y = PADDING + node.up;
+    let mut svg = Vec::new();
+
+    svg.push(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        width, height, width, height
+    ));
+    svg.push(STYLE.to_string());
+    svg.push(format!(
+        "<path d=\"M{} {}v20m4 -20v20M{} {}h{}\"/>",
+        x,
+        y - 10,
+        x + 4,
+        y,
+        STUB - 4
+    ));
+    node.draw(x + STUB, y, &mut svg);
+    svg.push(format!(
+        "<path d=\"M{} {}h{}m0 -10v20m4 -20v20\"/>",
+        x + STUB + node.width,
+        y,
+        STUB - 4
+    ));
+    svg.push("</svg>".to_string());
+
+    svg.join("\n") + "\n"
+}
+
+// -------------------------------------------------------------------------------------------------
+// Layout
+// -------------------------------------------------------------------------------------------------
+
+const STYLE: &str = "<style>\
+path{fill:none;stroke:#000;stroke-width:1.5}\
+rect{fill:#fff;stroke:#000;stroke-width:1.5}\
+rect.special{stroke-dasharray:4 2}\
+rect.group{fill:none;stroke:#888;stroke-dasharray:2 2}\
+text{font:14px monospace;text-anchor:middle}\
+text.label{font:10px sans-serif;text-anchor:start;fill:#888}\
+</style>";
+
+const PADDING: i32 = 20;
+const STUB: i32 = 20;
+const ARC: i32 = 10;
+const GAP: i32 = 10;
+const SPACE: i32 = 8;
+const CHAR: i32 = 8;
+const BOX: i32 = 11;
+const LABEL: i32 = 14;
+
+enum Kind {
+    Skip,
+    Terminal(String),
+    NonTerminal(String),
+    Special(String),
+    Sequence(Vec<Node>),
+    Choice(Vec<Node>),
+    Loop(Box<Node>),
+    Group(String, Box<Node>),
+}
+
+// A node is entered on the left and exited on the right at its baseline. Up and down give
+// its extent above and below the baseline.
+
+struct Node {
+    kind: Kind,
+    width: i32,
+    up: i32,
+    down: i32,
+}
+
+impl Node {
+    fn new(kind: Kind) -> Node {
+        let (width, up, down) = match &kind {
+            Kind::Skip => (0, 0, 0),
+            Kind::Terminal(text) | Kind::NonTerminal(text) | Kind::Special(text) => {
+                (text.chars().count() as i32 * CHAR + 2 * GAP, BOX, BOX)
+            }
+            Kind::Sequence(items) => (
+                items.iter().map(|n| n.width).sum::<i32>() + GAP * (items.len() as i32 - 1).max(0),
+                items.iter().map(|n| n.up).max().unwrap_or(0),
+                items.iter().map(|n| n.down).max().unwrap_or(0),
+            ),
+            Kind::Choice(items) => {
+                let offsets = offsets(items);
+                let last = items.len() - 1;
+
+                (
+                    items.iter().map(|n| n.width).max().unwrap_or(0) + 4 * ARC,
+                    items[0].up,
+                    offsets[last] + items[last].down,
+                )
+            }
+            Kind::Loop(item) => (item.width + 2 * ARC, item.up, depth(item)),
+            Kind::Group(_, item) => (item.width + 2 * GAP, item.up + GAP + LABEL, item.down + GAP),
+        };
+
+        Node {
+            kind,
+            width,
+            up,
+            down,
+        }
+    }
+
+    fn from(grammar: &Grammar) -> Node {
+        let boxed = |g: &Grammar| Box::new(Node::from(g));
+
+        Node::new(match grammar {
+            Grammar::Empty => Kind::Skip,
+            Grammar::Fail => Kind::Special("fail".to_string()),
+            Grammar::Any => Kind::Special("any".to_string()),
+            Grammar::Eos => Kind::Special("eos".to_string()),
+            Grammar::Literal(s) if s.is_empty() => Kind::Skip,
+            Grammar::Literal(s) => Kind::Terminal(s.escape_default().to_string()),
+            Grammar::CharClass(spec) => Kind::Special(format!("[{}]", spec.escape_default())),
+            Grammar::ByteClass(ranges) => Kind::Special(format!("byte {}", byte_ranges(ranges))),
+            Grammar::Seq(items) => Kind::Sequence(items.iter().map(Node::from).collect()),
+            Grammar::Alt(items) if items.is_empty() => Kind::Special("fail".to_string()),
+            Grammar::Alt(items) => Kind::Choice(items.iter().map(Node::from).collect()),
+            Grammar::Rep(g) => Kind::Loop(boxed(g)),
+            Grammar::OptRep(g) => {
+                Kind::Choice(vec![Node::new(Kind::Skip), Node::new(Kind::Loop(boxed(g)))])
+            }
+            Grammar::Opt(g) => Kind::Choice(vec![Node::new(Kind::Skip), Node::from(g)]),
+            Grammar::Lookahead(g) => Kind::Group("lookahead".to_string(), boxed(g)),
+            Grammar::Until(g) => Kind::Group("up to".to_string(), boxed(g)),
+            Grammar::Filter(g) => Kind::Group("filtered".to_string(), boxed(g)),
+            Grammar::Terminal(name) => Kind::Special(name.clone()),
+            Grammar::Named(name, _) | Grammar::Rule(name) => Kind::NonTerminal(name.clone()),
+            Grammar::Dynamic => Kind::Special("dynamic".to_string()),
+        })
+    }
+
+    fn draw(&self, x: i32, y: i32, svg: &mut Vec<String>) {
+        match &self.kind {
+            Kind::Skip => (),
+            Kind::Terminal(text) => draw_box(text, "terminal", x, y, self.width, svg),
+            Kind::NonTerminal(text) => draw_box(text, "nonterminal", x, y, self.width, svg),
+            Kind::Special(text) => draw_box(text, "special", x, y, self.width, svg),
+            Kind::Sequence(items) => {
+                let mut n = x;
+
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        svg.push(format!("<path d=\"M{} {}h{}\"/>", n, y, GAP));
+                        n += GAP;
+                    }
+                    item.draw(n, y, svg);
+                    n += item.width;
+                }
+            }
+            Kind::Choice(items) => {
+                let end = x + self.width;
+
+                for (item, dy) in items.iter().zip(offsets(items)) {
+                    let left = x + 2 * ARC;
+                    let right = left + item.width;
+
+                    if dy == 0 {
+                        svg.push(format!(
+                            "<path d=\"M{} {}H{}M{} {}H{}\"/>",
+                            x, y, left, right, y, end
+                        ));
+                    } else {
+                        svg.push(format!(
+                            "<path d=\"M{} {}a{a} {a} 0 0 1 {a} {a}v{}a{a} {a} 0 0 0 {a} {a}\
+                             M{} {}H{}a{a} {a} 0 0 0 {a} -{a}v-{}a{a} {a} 0 0 1 {a} -{a}\"/>",
+                            x,
+                            y,
+                            dy - 2 * ARC,
+                            right,
+                            y + dy,
+                            end - 2 * ARC,
+                            dy - 2 * ARC,
+                            a = ARC
+                        ));
+                    }
+
+                    item.draw(left, y + dy, svg);
+                }
+            }
+            Kind::Loop(item) => {
+                let left = x + ARC;
+                let right = left + item.width;
+                let dy = depth(item);
+
+                svg.push(format!(
+                    "<path d=\"M{} {}h{a}M{} {}h{a}\
+                     M{} {}a{a} {a} 0 0 1 {a} {a}v{}a{a} {a} 0 0 1 -{a} {a}H{}\
+                     a{a} {a} 0 0 1 -{a} -{a}v-{}a{a} {a} 0 0 1 {a} -{a}\"/>",
+                    x,
+                    y,
+                    right,
+                    y,
+                    right,
+                    y,
+                    dy - 2 * ARC,
+                    left,
+                    dy - 2 * ARC,
+                    a = ARC
+                ));
+                item.draw(left, y, svg);
+            }
+            Kind::Group(label, item) => {
+                let top = y - item.up - GAP;
+
+                svg.push(format!(
+                    "<rect class=\"group\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+                    x,
+                    top,
+                    self.width,
+                    item.up + item.down + 2 * GAP
+                ));
+                svg.push(format!(
+                    "<text class=\"label\" x=\"{}\" y=\"{}\">{}</text>",
+                    x,
+                    top - 4,
+                    escape(label)
+                ));
+                svg.push(format!(
+                    "<path d=\"M{} {}h{}M{} {}h{}\"/>",
+                    x,
+                    y,
+                    GAP,
+                    x + GAP + item.width,
+                    y,
+                    GAP
+                ));
+                item.draw(x + GAP, y, svg);
+            }
+        }
+    }
+}
+
+// Vertical offsets of the alternatives, the first one being on the baseline
+fn offsets(items: &[Node]) -> Vec<i32> {
+    let mut offsets = Vec::with_capacity(items.len());
+    let mut down = 0;
+
+    for (i, item) in items.iter().enumerate() {
+        let dy = if i == 0 {
+            0
+        } else {
+            (down + SPACE + item.up).max(2 * ARC)
+        };
+
+        offsets.push(dy);
+        down = dy + item.down;
+    }
+
+    offsets
+}
+
+// Vertical offset of the loop return path
+fn depth(item: &Node) -> i32 {
+    (item.down + SPACE).max(2 * ARC)
+}
+
+fn draw_box(text: &str, class: &str, x: i32, y: i32, width: i32, svg: &mut Vec<String>) {
+    let radius = if class == "terminal" { BOX } else { 0 };
+
+    svg.push(format!(
+        "<rect class=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"/>",
+        class,
+        x,
+        y - BOX,
+        width,
+        2 * BOX,
+        radius
+    ));
+    svg.push(format!(
+        "<text x=\"{}\" y=\"{}\">{}</text>",
+        x + width / 2,
+        y + 5,
+        escape(text)
+    ));
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// -------------------------------------------------------------------------------------------------
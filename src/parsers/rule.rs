@@ -0,0 +1,422 @@
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::parsers::core::*;
+use crate::parsers::execution::*;
+use crate::parsers::grammar::*;
+use crate::parsers::lint::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Rule reference definition
+// -------------------------------------------------------------------------------------------------
+
+// Every reference keeps the rule alive, whatever the definition order. References given to a
+// definition are owned by it: they are found by walking the definition with `check`. When a
+// rule is only referenced by definitions, the rules reachable from it are scanned and the
+// definitions of the unreachable cycles are dropped.
+
+struct Node<'a, A> {
+    parser: RefCell<Option<Parsec<'a, A>>>,
+    seeds: RefCell<HashMap<usize, Response<A>>>,
+    owned: Cell<usize>,
+    visiting: Cell<bool>,
+}
+
+struct Link<'a, A> {
+    node: Rc<Node<'a, A>>,
+    owned: Cell<bool>,
+}
+
+enum Walk {
+    // References found in a new definition are owned by it
+    Adopt,
+    // Rule identifiers with their references and the references found in definitions
+    Scan(
+        HashMap<usize, (usize, usize)>,
+        Vec<(usize, usize)>,
+        Vec<usize>,
+    ),
+    // Definitions of unreachable rules are dropped
+    Clear(HashSet<usize>, HashSet<usize>),
+}
+
+thread_local! {
+    static WALK: RefCell<Option<Walk>> = const { RefCell::new(None) };
+}
+
+fn walk<R, F>(walk: Walk, f: F) -> (R, Option<Walk>)
+where
+    F: FnOnce() -> R,
+{
+    WALK.with(|w| w.replace(Some(walk)));
+    let result = f();
+
+    (result, WALK.with(|w| w.replace(None)))
+}
+
+// Parsers built on demand like `lazy` ones are not part of a definition
+
+pub(crate) fn walking() -> bool {
+    WALK.with(|w| w.borrow().is_some())
+}
+
+impl<'a, A> Link<'a, A> {
+    fn new() -> Self {
+        Link {
+            node: Rc::new(Node {
+                parser: RefCell::new(None),
+                seeds: RefCell::new(HashMap::new()),
+                owned: Cell::new(0),
+                visiting: Cell::new(false),
+            }),
+            owned: Cell::new(false),
+        }
+    }
+
+    fn id(&self) -> usize {
+        Rc::as_ptr(&self.node) as usize
+    }
+
+    fn define<E>(&self, p: E)
+    where
+        E: Executable<'a, A> + 'a,
+    {
+        walk(Walk::Adopt, || p.check(&mut Vec::new()));
+
+        let previous = self.node.parser.replace(Some(parsec(Box::new(p))));
+
+        drop(previous);
+    }
+
+    fn visit(&self) {
+        if let Some(p) = self.node.parser.borrow().as_ref() {
+            p.check(&mut Vec::new());
+        }
+    }
+
+    // A reference met during a walk is handled by the walk instead of a lint check
+
+    fn walked(&self) -> bool {
+        let id = self.id();
+        let step = WALK.with(|w| match w.borrow_mut().as_mut() {
+            None => None,
+            Some(Walk::Adopt) => {
+                if !self.owned.replace(true) {
+                    self.node.owned.set(self.node.owned.get() + 1);
+                }
+                Some(false)
+            }
+            Some(Walk::Scan(references, edges, path)) => {
+                edges.push((path[path.len() - 1], id));
+
+                let visited = references.contains_key(&id);
+                let strong = Rc::strong_count(&self.node);
+                references.entry(id).or_insert((strong, 0)).1 += 1;
+
+                if !visited {
+                    path.push(id);
+                }
+                Some(!visited)
+            }
+            Some(Walk::Clear(garbage, cleared)) => {
+                Some(garbage.contains(&id) && cleared.insert(id))
+            }
+        });
+
+        match step {
+            None => false,
+            Some(false) => true,
+            Some(true) => {
+                self.visit();
+
+                let scanned = WALK.with(|w| match w.borrow_mut().as_mut() {
+                    Some(Walk::Scan(_, _, path)) => path.pop().is_some(),
+                    _ => false,
+                });
+
+                if !scanned {
+                    self.node.parser.replace(None);
+                }
+
+                true
+            }
+        }
+    }
+
+    fn collect(&self) {
+        let id = self.id();
+        let references = HashMap::from([(id, (Rc::strong_count(&self.node) - 1, 0))]);
+        let scan = Walk::Scan(references, Vec::new(), vec![id]);
+
+        let (references, edges) = match walk(scan, || self.visit()).1 {
+            Some(Walk::Scan(references, edges, _)) => (references, edges),
+            _ => return,
+        };
+
+        // Rules referenced from outside of the scanned definitions are alive like the rules
+        // they reach
+
+        let mut alive = references
+            .iter()
+            .filter(|(_, (strong, found))| strong > found)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        let mut reached = alive.iter().cloned().collect::<HashSet<_>>();
+
+        while let Some(from) = alive.pop() {
+            for (_, to) in edges.iter().filter(|(f, _)| *f == from) {
+                if reached.insert(*to) {
+                    alive.push(*to);
+                }
+            }
+        }
+
+        if reached.contains(&id) {
+            return;
+        }
+
+        let garbage = references
+            .keys()
+            .filter(|id| !reached.contains(id))
+            .cloned()
+            .collect();
+
+        walk(Walk::Clear(garbage, HashSet::from([id])), || {
+            self.visit();
+            self.node.parser.replace(None);
+        });
+    }
+}
+
+impl<'a, A> Clone for Link<'a, A> {
+    fn clone(&self) -> Self {
+        Link {
+            node: self.node.clone(),
+            owned: Cell::new(false),
+        }
+    }
+}
+
+impl<'a, A> Drop for Link<'a, A> {
+    fn drop(&mut self) {
+        let owned = self.node.owned.get() - self.owned.get() as usize;
+        let references = Rc::strong_count(&self.node) - 1;
+
+        self.node.owned.set(owned);
+
+        if references > 0 && references == owned && !walking() {
+            self.collect();
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+pub struct LeftRec<'a, A>(Link<'a, A>);
+
+impl<'a, A> Parser<A> for LeftRec<'a, A> {}
+
+impl<'a, A> Clone for LeftRec<'a, A> {
+    fn clone(&self) -> Self {
+        let LeftRec(link) = self;
+
+        LeftRec(link.clone())
+    }
+}
+
+pub fn left_rec<'a, A, E, F>(f: F) -> LeftRec<'a, A>
+where
+    E: Executable<'a, A> + 'a,
+    F: FnOnce(LeftRec<'a, A>) -> E,
+{
+    let rule = LeftRec(Link::new());
+    let parser = f(rule.clone());
+
+    rule.0.define(parser);
+
+    rule
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub struct Rule<'a, A>(Link<'a, A>);
+
+impl<'a, A> Parser<A> for Rule<'a, A> {}
+
+impl<'a, A> Clone for Rule<'a, A> {
+    fn clone(&self) -> Self {
+        let Rule(link) = self;
+
+        Rule(link.clone())
+    }
+}
+
+impl<'a, A> Rule<'a, A> {
+    pub fn define<E>(&self, p: E)
+    where
+        E: Executable<'a, A> + 'a,
+    {
+        self.0.define(p)
+    }
+}
+
+#[inline]
+pub fn rule<'a, A>() -> Rule<'a, A> {
+    Rule(Link::new())
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+// Seed growing: a recursive call at the same offset returns the current seed. The seed
+// starts as a failure and is replaced while the rule body consumes more input.
+
+impl<'a, A> Executable<'a, A> for LeftRec<'a, A>
+where
+    A: Clone,
+{
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+        let LeftRec(link) = self;
+        let node = &link.node;
+        let parser = node.parser.borrow();
+        let parser = match parser.as_ref() {
+            Some(parser) => parser,
+            None => return response(None, o, false),
+        };
+
+        if let Some(seed) = node.seeds.borrow().get(&o) {
+            return response(seed.v.clone(), seed.o, seed.c);
+        }
+
+        node.seeds.borrow_mut().insert(o, response(None, o, false));
+
+        let mut result = parser.execute(s, o);
+
+        if result.v.is_some() {
+            loop {
+                node.seeds
+                    .borrow_mut()
+                    .insert(o, response(result.v.clone(), result.o, result.c));
+
+                let r = parser.execute(s, o);
+
+                if r.v.is_none() || r.o <= result.o {
+                    break;
+                }
+
+                result = r;
+            }
+        }
+
+        node.seeds.borrow_mut().remove(&o);
+
+        result
+    }
+
+    fn nullable(&self) -> bool {
+        self.0.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        expanding(format!("left_rec {:p}", self.0.node), || self.0.describe())
+    }
+}
+
+impl<'a, A> Parsable<'a, A> for LeftRec<'a, A>
+where
+    A: Clone,
+{
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, A> Executable<'a, A> for Rule<'a, A> {
+    fn execute(&self, s: &'a [u8], o: usize) -> Response<A> {
+        let Rule(link) = self;
+        let parser = link.node.parser.borrow();
+
+        match parser.as_ref() {
+            Some(p) => p.execute(s, o),
+            None => response(None, o, false),
+        }
+    }
+
+    fn nullable(&self) -> bool {
+        self.0.nullable()
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        self.0.check(warnings)
+    }
+
+    fn describe(&self) -> Grammar {
+        expanding(format!("rule {:p}", self.0.node), || self.0.describe())
+    }
+}
+
+impl<'a, A> Parsable<'a, A> for Rule<'a, A> {
+    fn parse_only(&self, s: &'a [u8], o: usize) -> Response<()> {
+        self.execute(s, o).fmap(|_| ())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Rule analysis
+// -------------------------------------------------------------------------------------------------
+
+// Recursive references are not analysed twice
+
+impl<'a, A> Link<'a, A> {
+    fn nullable(&self) -> bool {
+        if self.node.visiting.replace(true) {
+            return false;
+        }
+
+        let nullable = self
+            .node
+            .parser
+            .borrow()
+            .as_ref()
+            .is_some_and(|p| p.nullable());
+        self.node.visiting.set(false);
+
+        nullable
+    }
+
+    fn check(&self, warnings: &mut Vec<Warning>) {
+        if self.walked() || self.node.visiting.replace(true) {
+            return;
+        }
+
+        if let Some(p) = self.node.parser.borrow().as_ref() {
+            p.check(warnings);
+        }
+
+        self.node.visiting.set(false);
+    }
+
+    fn describe(&self) -> Grammar {
+        self.node
+            .parser
+            .borrow()
+            .as_ref()
+            .map_or(Grammar::Dynamic, |p| p.describe())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,113 @@
+use core::marker::PhantomData;
+
+use crate::parsers::execution::*;
+use crate::parsers::parser::*;
+use crate::parsers::response::*;
+
+// -------------------------------------------------------------------------------------------------
+// Parser type definition
+// -------------------------------------------------------------------------------------------------
+
+pub struct MapState<E, A, B, S, F>(E, F, PhantomData<(A, B, S)>)
+where
+    E: Parser<A>;
+
+impl<E, A, B, S, F> Parser<B> for MapState<E, A, B, S, F> where E: Parser<A> {}
+
+pub trait MapStateOperation<E, A>
+where
+    E: Parser<A>,
+{
+    fn map_state<B, S, F>(self, f: F) -> MapState<E, A, B, S, F>
+    where
+        F: Fn(A, &mut S) -> B;
+}
+
+impl<E, A> MapStateOperation<E, A> for E
+where
+    E: Parser<A>,
+{
+    #[inline]
+    fn map_state<B, S, F>(self, f: F) -> MapState<E, A, B, S, F>
+    where
+        F: Fn(A, &mut S) -> B,
+    {
+        MapState(self, f, PhantomData)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub struct FilterState<E, A, S, F>(E, F, PhantomData<(A, S)>)
+where
+    E: Parser<A>;
+
+impl<E, A, S, F> Parser<A> for FilterState<E, A, S, F> where E: Parser<A> {}
+
+pub trait FilterStateOperation<E, A>
+where
+    E: Parser<A>,
+{
+    fn filter_state<S, F>(self, f: F) -> FilterState<E, A, S, F>
+    where
+        F: Fn(&A, &S) -> bool;
+}
+
+impl<E, A> FilterStateOperation<E, A> for E
+where
+    E: Parser<A>,
+{
+    #[inline]
+    fn filter_state<S, F>(self, f: F) -> FilterState<E, A, S, F>
+    where
+        F: Fn(&A, &S) -> bool,
+    {
+        FilterState(self, f, PhantomData)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Parser execution
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, B, S, F> StatefulExecutable<'a, B, S> for MapState<E, A, B, S, F>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    F: Fn(A, &mut S) -> B,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<B> {
+        let MapState(parser, function, _) = self;
+        let result = parser.execute_with(s, o, state);
+
+        match result.v {
+            Some(value) => response(Some(function(value, state)), result.o, result.c),
+            _ => response(None, result.o, result.c),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl<'a, E, A, S, F> StatefulExecutable<'a, A, S> for FilterState<E, A, S, F>
+where
+    E: StatefulExecutable<'a, A, S> + Parser<A>,
+    F: Fn(&A, &S) -> bool,
+{
+    fn execute_with(&self, s: &'a [u8], o: usize, state: &mut S) -> Response<A> {
+        let FilterState(parser, predicate, _) = self;
+        let result = parser.execute_with(s, o, state);
+
+        match result.v {
+            Some(value) => {
+                if predicate(&value, state) {
+                    response(Some(value), result.o, result.c)
+                } else {
+                    response(None, result.o, result.c)
+                }
+            }
+            _ => result,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
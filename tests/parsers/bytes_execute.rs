@@ -0,0 +1,64 @@
+extern crate parsecute;
+
+use parsecute::parsers::bytes::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::literal::*;
+use parsecute::parsers::response::*;
+
+#[test]
+fn it_skip_with_small_byte_set() {
+    let spaces = ByteSet::from_bytes(b" \t\r\n");
+    let input = b"  \t\r\n      \n\n    \t      x  ";
+
+    assert_eq!(24, spaces.skip(input, 0));
+    assert_eq!(24, spaces.skip(input, 7));
+    assert_eq!(27, spaces.skip(input, 25));
+    assert_eq!(27, spaces.skip(input, 27));
+}
+
+#[test]
+fn it_skip_with_large_byte_set() {
+    let letters = ByteSet::from_fn(|b| b.is_ascii_alphabetic());
+
+    assert_eq!(52, letters.len());
+    assert_eq!(20, letters.skip(b"abcdefghijABCDEFGHIJ0", 0));
+}
+
+#[test]
+fn it_find_with_byte_set() {
+    let delimiters = ByteSet::from_bytes(b"\"\\");
+    let input = b"a long string without delimiters then \\\" and more";
+
+    assert_eq!(Some(38), delimiters.find(input, 0));
+    assert_eq!(Some(39), delimiters.find(input, 39));
+    assert_eq!(None, delimiters.find(input, 40));
+    assert_eq!(None, delimiters.complement().find(b"\"\"\"\"\"\"\"\"\"", 0));
+}
+
+#[test]
+fn it_execute_take_while_with_byte_set() {
+    let r = take_while(|c| c.is_ascii_digit());
+
+    assert_eq!(
+        (b"0123456789".to_vec(), 10),
+        r.execute(b"0123456789abc", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_execute_delimited_string_with_escapes() {
+    let r = delimited_string();
+
+    assert_eq!(
+        b"a long \\\"escaped\\\" string".to_vec(),
+        r.execute(b"\"a long \\\"escaped\\\" string\" rest", 0)
+            .fold(|a, _, _| a.to_vec(), |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(
+        (9, true),
+        r.execute(b"\"unclosed", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, c| (o, c))
+    );
+}
@@ -0,0 +1,100 @@
+extern crate parsecute;
+
+use parsecute::parsers::charset::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::response::*;
+
+#[test]
+fn it_execute_one_of_bytes() {
+    let r = one_of(b"+-*/").optrep();
+
+    assert_eq!(
+        (b"+-*".to_vec(), 3),
+        r.execute(b"+-*a", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_execute_none_of_bytes() {
+    let r = none_of(b"\"\\");
+
+    assert!(r
+        .execute(b"\\", 0)
+        .fold(|_, _, _| false, |o, c| o == 0 && !c));
+    assert_eq!(
+        b'a',
+        r.execute(b"a", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_execute_none_of_chars() {
+    let r = none_of("\"\\é")
+        .optrep()
+        .fmap(|v| v.into_iter().collect::<String>());
+
+    assert_eq!(
+        ("aà€".to_string(), 6),
+        r.execute("aà€é".as_bytes(), 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_execute_char_class() {
+    let r = char_class("a-zA-Z0-9_")
+        .rep()
+        .fmap(|v| v.into_iter().collect::<String>());
+
+    assert_eq!(
+        "snake_Case42".to_string(),
+        r.execute(b"snake_Case42 = 1", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_execute_char_class_with_unicode_ranges() {
+    let r = char_class("α-ωа-я\\--");
+
+    assert_eq!(
+        ('λ', 2),
+        r.execute("λ".as_bytes(), 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(
+        '-',
+        r.execute(b"-", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+    assert!(r.execute(b"a", 0).fold(|_, _, _| false, |_, _| true));
+}
+
+#[test]
+fn it_execute_negated_char_class() {
+    let r = char_class("^0-9");
+
+    assert!(r.execute(b"7", 0).fold(|_, _, _| false, |_, _| true));
+    assert_eq!(
+        'é',
+        r.execute("é".as_bytes(), 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_reject_invalid_char_class() {
+    assert_eq!(
+        Some("invalid character class: z-a".to_string()),
+        try_char_class("z-a").err()
+    );
+    assert_eq!(
+        Some("invalid character class: trailing escape".to_string()),
+        try_char_class("a\\").err()
+    );
+    assert!(try_char_class("a-z").is_ok());
+}
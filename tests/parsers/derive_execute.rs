@@ -0,0 +1,124 @@
+extern crate parsecute;
+
+use std::cell::Cell;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::core::*;
+use parsecute::parsers::derive::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::literal::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::response::*;
+
+fn identifier<'a>() -> impl Executable<'a, String> {
+    letter().rep().fmap(|v: Vec<char>| v.into_iter().collect())
+}
+
+#[derive(Debug, PartialEq, Parse)]
+struct Number(#[parse(with = digit().rep().fmap(|v: Vec<char>| v.into_iter().collect()))] String);
+
+#[derive(Debug, PartialEq, Parse)]
+#[parse(whitespace = " ")]
+enum Expr {
+    #[parse("let" name "=" value "in" body)]
+    Let {
+        #[parse(with = identifier())]
+        name: String,
+        value: Box<Expr>,
+        body: Box<Expr>,
+    },
+    #[parse("(" 0 ")")]
+    List(#[parse(sep = ",")] Vec<Expr>),
+    Num(Number),
+    Var(#[parse(with = identifier())] String),
+}
+
+fn parse(s: &str) -> Option<Expr> {
+    Expr::parser()
+        .then_left(eos())
+        .execute(s.as_bytes(), 0)
+        .fold(|a, _, _| Some(a), |_, _| None)
+}
+
+#[test]
+fn it_derive_tuple_struct() {
+    assert_eq!(
+        Number("42".to_string()),
+        Number::parser()
+            .execute(b"42", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_derive_enum_alternatives() {
+    assert_eq!(Some(Expr::Num(Number("12".to_string()))), parse("12"));
+    assert_eq!(Some(Expr::Var("x".to_string())), parse("x"));
+    assert_eq!(None, parse("+"));
+}
+
+#[test]
+fn it_derive_pattern_with_tokens() {
+    assert_eq!(
+        Some(Expr::Let {
+            name: "x".to_string(),
+            value: Box::new(Expr::Num(Number("1".to_string()))),
+            body: Box::new(Expr::Var("x".to_string())),
+        }),
+        parse("let x = 1 in x")
+    );
+}
+
+#[test]
+fn it_derive_separated_fields() {
+    assert_eq!(Some(Expr::List(vec![])), parse("()"));
+    assert_eq!(
+        Some(Expr::List(vec![
+            Expr::Var("a".to_string()),
+            Expr::List(vec![Expr::Num(Number("1".to_string()))]),
+        ])),
+        parse("(a, (1))")
+    );
+    assert_eq!(
+        Some(Expr::List(vec![
+            Expr::Var("x".to_string()),
+            Expr::Var("x".to_string()),
+        ])),
+        parse("(x ,x)")
+    );
+    assert_eq!(
+        Some(Expr::List(vec![Expr::Var("x".to_string())])),
+        parse("( x )")
+    );
+}
+
+thread_local! {
+    static BUILT: Cell<usize> = const { Cell::new(0) };
+}
+
+struct Digit;
+
+impl<'a> Parse<'a> for Digit {
+    fn parser() -> Parsec<'a, Self> {
+        BUILT.with(|b| b.set(b.get() + 1));
+        Parsec(Box::new(digit().fmap(|_| Digit)))
+    }
+}
+
+#[test]
+fn it_build_derived_parser_once() {
+    let p = parsed::<Vec<Digit>>();
+
+    assert_eq!(
+        3,
+        p.execute(b"123", 0)
+            .fold(|a, _, _| a.len(), |_, _| panic!("Parse error"))
+    );
+    assert_eq!(
+        2,
+        p.execute(b"45", 0)
+            .fold(|a, _, _| a.len(), |_, _| panic!("Parse error"))
+    );
+    assert_eq!(1, BUILT.with(|b| b.get()));
+}
@@ -0,0 +1,119 @@
+extern crate parsecute;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::charset::*;
+use parsecute::parsers::ebnf::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::grammar::*;
+use parsecute::parsers::literal::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::rule::*;
+
+#[test]
+fn it_export_recursive_rule() {
+    let list = rule();
+
+    list.define(
+        '('.then_right(list.clone().optrep())
+            .then_left(')')
+            .fmap(|v: Vec<usize>| v.len())
+            .or('x'.fmap(|_| 0))
+            .named("list"),
+    );
+
+    assert_eq!(
+        "list = \"(\", { list }, \")\" | \"x\" ;\n",
+        ebnf(&list, Notation::Iso)
+    );
+    assert_eq!(
+        "list ::= \"(\" list* \")\" | \"x\"\n",
+        ebnf(&list, Notation::W3c)
+    );
+}
+
+#[test]
+fn it_export_named_rules() {
+    let p = digit()
+        .rep()
+        .named("number")
+        .then('+'.or('-').then(digit().rep().named("number")).optrep())
+        .then(eos());
+
+    assert_eq!(
+        "grammar = number, { (\"+\" | \"-\"), number }, ? end of input ? ;\n\
+         number = ? [0-9] ?, { ? [0-9] ? } ;\n",
+        ebnf(&p, Notation::Iso)
+    );
+    assert_eq!(
+        "grammar ::= number ((\"+\" | \"-\") number)* /* end of input */\n\
+         number ::= [0-9]+\n",
+        ebnf(&p, Notation::W3c)
+    );
+}
+
+#[test]
+fn it_export_literals() {
+    let p = "say \"hi\" it's\n";
+
+    assert_eq!(
+        "grammar = \"say \", '\"', \"hi\", '\"', \" it's\", ? U+000A ? ;\n",
+        ebnf(&p, Notation::Iso)
+    );
+    assert_eq!(
+        "grammar ::= (\"a\" #x0A)?\n",
+        ebnf(&"a\n".opt(), Notation::W3c)
+    );
+}
+
+#[test]
+fn it_export_character_classes() {
+    assert_eq!(
+        "grammar ::= [^#x2D#x5D]\n",
+        ebnf(&none_of("]-"), Notation::W3c)
+    );
+    assert_eq!(
+        "grammar ::= [#x09#x0A#x20]\n",
+        ebnf(&one_of(b" \t\n"), Notation::W3c)
+    );
+    assert_eq!(
+        "grammar = ? [\\t\\n ] ? ;\n",
+        ebnf(&one_of(b" \t\n"), Notation::Iso)
+    );
+}
+
+#[test]
+fn it_export_take_until() {
+    let p = "/*".then(take_until("*/")).then("*/");
+
+    assert_eq!(
+        "grammar = \"/*\", ? bytes up to \"*/\" ?, \"*/\" ;\n",
+        ebnf(&p, Notation::Iso)
+    );
+    assert_eq!(
+        "grammar ::= \"/*\" /* bytes up to \"* /\" */ \"*/\"\n",
+        ebnf(&p, Notation::W3c)
+    );
+}
+
+#[test]
+fn it_export_byte_classes() {
+    let p = 0xffu8.then(take_while(|b| *b != b'"'));
+
+    assert_eq!(
+        "grammar = ? byte [#xFF] ?, { ? byte [^\"] ? } ;\n",
+        ebnf(&p, Notation::Iso)
+    );
+    assert_eq!("grammar ::= [#xFF] [^\"]*\n", ebnf(&p, Notation::W3c));
+}
+
+#[test]
+fn it_export_optional_alternative() {
+    let p = 'a'.or(returns('b'));
+
+    assert_eq!("grammar = [ \"a\" ] ;\n", ebnf(&p, Notation::Iso));
+    assert_eq!("grammar ::= \"a\"?\n", ebnf(&p, Notation::W3c));
+    assert_eq!(
+        "grammar ::= /* dynamic */\n",
+        to_ebnf(&Grammar::Dynamic, Notation::W3c)
+    );
+}
@@ -1,8 +1,10 @@
 extern crate parsecute;
 
 use parsecute::parsers::basic::*;
+use parsecute::parsers::core::*;
 use parsecute::parsers::execution::*;
 use parsecute::parsers::flow::*;
+use parsecute::parsers::monadic::*;
 use parsecute::parsers::response::*;
 
 #[test]
@@ -143,3 +145,209 @@ fn it_parse_with_take_while_consumed() {
             .fold(|_, _, b| b, |_, _| panic!("Parse error"),)
     );
 }
+
+#[test]
+fn it_parse_with_take_while_out_of_input() {
+    let r = take_while(|a| *a as char != 'b');
+
+    assert!(r.execute(b"a", 2).fold(|_, _, _| false, |_, _| true));
+}
+
+#[test]
+fn it_parse_with_many_till() {
+    let r = any().many_till("*/");
+
+    assert_eq!(
+        (b"a comment ".to_vec(), 12),
+        r.execute(b"a comment */", 0)
+            .fold(|(a, _), o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_many_till_empty() {
+    let r = many_till(any(), '.');
+
+    assert_eq!(
+        (0, '.'),
+        r.execute(b".", 0)
+            .fold(|(a, b), _, _| (a.len(), b), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_many_till_reject() {
+    let r = 'a'.many_till('.');
+
+    assert_eq!(
+        2,
+        r.execute(b"aab.", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o,)
+    );
+}
+
+#[test]
+fn it_parse_with_take_until() {
+    let r = take_until("*/");
+
+    assert_eq!(
+        b" comment * ".to_vec(),
+        r.execute(b" comment * */", 0)
+            .fold(|a, _, _| a.to_vec(), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_take_until_reject() {
+    let r = take_until("*/");
+
+    assert_eq!(
+        0,
+        r.execute(b" comment * /", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o,)
+    );
+}
+
+#[test]
+fn it_parse_with_take_until_overlapping_terminator() {
+    let r = take_until("abac");
+
+    assert_eq!(
+        b"aab".to_vec(),
+        r.execute(b"aababac", 0)
+            .fold(|a, _, _| a.to_vec(), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_take_until_any() {
+    let r = take_until_any(b"\r\n");
+
+    assert_eq!(
+        (b"Host: localhost".to_vec(), 15),
+        r.execute(b"Host: localhost\r\n", 0)
+            .fold(|a, o, _| (a.to_vec(), o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_choice() {
+    let r = choice(('a', 'b', 'c'));
+
+    assert_eq!(
+        'c',
+        r.execute(b"c", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_choice_reject() {
+    let r = choice(("ab", "ac", "ad"));
+
+    assert_eq!(
+        (0, false),
+        r.execute(b"ae", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, c| (o, c),)
+    );
+}
+
+#[test]
+fn it_parse_with_choice_consumed_reject() {
+    let r = choice(("a".then_left('b'), "ac"));
+
+    assert!(r
+        .execute(b"ac", 0)
+        .fold(|_, _, _| panic!("Parse error"), |_, c| c,));
+}
+
+#[test]
+fn it_parse_with_choice_vec() {
+    let r = choice_vec(vec![parsec!("ab"), parsec!("ac"), parsec!("ad")]);
+
+    assert_eq!(
+        "ad",
+        r.execute(b"ad", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_tuple() {
+    let r = tuple(('a', any(), "cd"));
+
+    assert_eq!(
+        ('a', b'b', "cd"),
+        r.execute(b"abcd", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_tuple_reject() {
+    let r = tuple(('a', any(), "cd"));
+
+    assert_eq!(
+        (2, true),
+        r.execute(b"abce", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, c| (o, c),)
+    );
+}
+
+#[test]
+fn it_parse_with_dispatch() {
+    let r = dispatch(
+        vec![
+            (b"t", parsec!("true".fmap(|_| 1))),
+            (b"f", parsec!("false".fmap(|_| 0))),
+        ],
+        parsec!(returns(-1)),
+    );
+
+    assert_eq!(
+        (0, 5),
+        r.execute(b"false", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_dispatch_default() {
+    let r = dispatch(
+        vec![(b"tT", parsec!("true".fmap(|_| 1)))],
+        parsec!(returns(-1)),
+    );
+
+    assert_eq!(
+        (-1, 0),
+        r.execute(b"null", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_dispatch_after_many_cases() {
+    let mut cases: Vec<(&[u8], Parsec<i32>)> = (0..70_000)
+        .map(|_| (&b""[..], parsec!(returns(0))))
+        .collect();
+    cases.push((b"t", parsec!("true".fmap(|_| 1))));
+
+    let r = dispatch(cases, parsec!(returns(-1)));
+
+    assert_eq!(
+        (1, 4),
+        r.execute(b"true", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_dispatch_end_of_stream() {
+    let r = dispatch(vec![(b"t", parsec!("true".fmap(|_| 1)))], parsec!(fail()));
+
+    assert_eq!(
+        0,
+        r.execute(b"", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o,)
+    );
+}
@@ -139,3 +139,58 @@ fn it_parse_with_take_while_consumed() {
             .fold(|_, _, b| b, |_, _| panic!("Parse error"),)
     );
 }
+
+#[test]
+fn it_parse_with_many_till() {
+    let r = any().many_till("*/");
+
+    assert_eq!(
+        12,
+        r.parse_only(b"a comment */", 0)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_take_until_empty() {
+    let r = take_until("*/");
+
+    assert_eq!(
+        (0, false),
+        r.parse_only(b"*/", 0)
+            .fold(|_, o, c| (o, c), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_take_until_any() {
+    let r = take_until_any(b"\r\n");
+
+    assert_eq!(
+        5,
+        r.parse_only(b"hello\nworld", 0)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_choice() {
+    let r = choice(('a', 'b', 'c'));
+
+    assert_eq!(
+        1,
+        r.parse_only(b"b", 0)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_tuple() {
+    let r = tuple(('a', any(), "cd"));
+
+    assert_eq!(
+        4,
+        r.parse_only(b"abcd", 0)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+}
@@ -0,0 +1,130 @@
+extern crate parsecute;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::charset::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::generate::*;
+use parsecute::parsers::grammar::*;
+use parsecute::parsers::literal::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::peg::*;
+use parsecute::parsers::rule::*;
+
+fn accepted<'a, E, A>(p: &E, s: &'a [u8]) -> bool
+where
+    E: Executable<'a, A>,
+{
+    let r = p.execute(s, 0);
+
+    r.v.is_some() && r.o == s.len()
+}
+
+#[test]
+fn it_generate_same_sentences_for_a_seed() {
+    let p = 'a'.or('b').optrep().then("c");
+    let generation = Generation {
+        seed: 42,
+        ..Generation::default()
+    };
+    let sentences = |g: &mut Generator| (0..8).map(|_| g.sentence()).collect::<Vec<_>>();
+
+    assert_eq!(
+        sentences(&mut generator(&p, generation)),
+        sentences(&mut generator(&p, generation))
+    );
+}
+
+// Sentences are generated with a first parser and checked with a second one which can borrow them
+
+fn sentences<'a, E, A>(p: &E, generation: Generation) -> Vec<Vec<u8>>
+where
+    E: Executable<'a, A>,
+{
+    (0..100)
+        .map(|seed| {
+            generator(p, Generation { seed, ..generation })
+                .sentence()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn list<'a>() -> Rule<'a, usize> {
+    let list = rule();
+
+    list.define(
+        '['.then_right(list.clone().optrep())
+            .then_left(']')
+            .fmap(|v: Vec<usize>| v.len())
+            .or(float().then_left(';').fmap(|_| 0))
+            .named("list"),
+    );
+
+    list
+}
+
+#[test]
+fn it_generate_sentences_accepted_by_recursive_rule() {
+    let generation = Generation {
+        depth: 4,
+        repeat: 3,
+        ..Generation::default()
+    };
+    let sentences = sentences(&list(), generation);
+    let list = list();
+
+    for sentence in &sentences {
+        assert!(accepted(&list, sentence), "{:?}", sentence);
+    }
+}
+
+const EXPR: &str = r#"
+    expr   <- term (("+" / "-") term)*
+    term   <- [0-9]+ / "(" expr ")" / string
+    string <- "\"" [^"\\]* "\""
+"#;
+
+#[test]
+fn it_generate_sentences_accepted_by_loaded_grammar() {
+    let sentences = sentences(&load(EXPR).unwrap(), Generation::default());
+    let p = load(EXPR).unwrap();
+
+    for sentence in &sentences {
+        assert!(accepted(&p, sentence), "{:?}", sentence);
+    }
+}
+
+#[test]
+fn it_generate_sentences_accepted_by_take_until() {
+    let p = "/*"
+        .then(take_until("*/"))
+        .then("*/")
+        .then(take_until_any(b";,"))
+        .then(';');
+
+    for sentence in &sentences(&p, Generation::default()) {
+        assert!(accepted(&p, sentence), "{:?}", sentence);
+    }
+}
+
+#[test]
+fn it_generate_raw_bytes() {
+    let p = 0xffu8.then(one_of(&b"\xe9"[..])).then(b'a');
+
+    assert_eq!(
+        Some(vec![0xff, 0xe9, b'a']),
+        generator(&p, Generation::default()).sentence()
+    );
+}
+
+#[test]
+fn it_reject_undescribed_grammar() {
+    let p = any().bind(returns);
+
+    assert_eq!(None, generator(&p, Generation::default()).sentence());
+    assert_eq!(
+        None,
+        Generator::new(&Grammar::Fail, Generation::default()).sentence()
+    );
+}
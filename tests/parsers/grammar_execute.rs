@@ -0,0 +1,144 @@
+extern crate parsecute;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::charset::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::grammar::*;
+use parsecute::parsers::literal::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::rule::*;
+
+fn literal(s: &str) -> Grammar {
+    Grammar::Literal(s.to_string())
+}
+
+#[test]
+fn it_describe_sequences_and_alternatives() {
+    assert_eq!(
+        Grammar::Seq(vec![literal("a"), literal("b"), literal("c")]),
+        describe(&'a'.then('b').then('c'))
+    );
+    assert_eq!(
+        Grammar::Alt(vec![literal("a"), literal("b"), literal("c")]),
+        describe(&'a'.or('b').or('c'))
+    );
+    assert_eq!(
+        Grammar::Seq(vec![literal("("), Grammar::Any, literal(")")]),
+        describe(&'('.then_right(any()).then_left(')'))
+    );
+}
+
+#[test]
+fn it_describe_repetitions() {
+    assert_eq!(Grammar::Rep(Box::new(literal("a"))), describe(&'a'.rep()));
+    assert_eq!(
+        Grammar::OptRep(Box::new(literal("a"))),
+        describe(&'a'.optrep())
+    );
+    assert_eq!(Grammar::Opt(Box::new(literal("a"))), describe(&'a'.opt()));
+    assert_eq!(
+        Grammar::Seq(vec![Grammar::OptRep(Box::new(Grammar::Any)), literal("*/")]),
+        describe(&any().many_till("*/"))
+    );
+}
+
+#[test]
+fn it_describe_character_classes() {
+    assert_eq!(Grammar::CharClass("0-9".to_string()), describe(&digit()));
+    assert_eq!(
+        Grammar::CharClass("_a-z".to_string()),
+        describe(&char_class("a-z_"))
+    );
+    assert_eq!(
+        Grammar::CharClass("^\\-\\]".to_string()),
+        describe(&none_of("]-"))
+    );
+    assert_eq!(
+        Grammar::CharClass("\t\n ".to_string()),
+        describe(&one_of(b" \t\n"))
+    );
+    assert_eq!(
+        Grammar::OptRep(Box::new(Grammar::CharClass("ab".to_string()))),
+        describe(&skip("ab".to_string()))
+    );
+}
+
+#[test]
+fn it_describe_bytes() {
+    assert_eq!(literal("a"), describe(&b'a'));
+    assert_eq!(Grammar::ByteClass(vec![(0xff, 0xff)]), describe(&0xffu8));
+    assert_eq!(
+        Grammar::ByteClass(vec![(0xe9, 0xe9)]),
+        describe(&one_of(&b"\xe9"[..]))
+    );
+    assert_eq!(
+        Grammar::OptRep(Box::new(Grammar::ByteClass(vec![(0, 0x21), (0x23, 0xff)]))),
+        describe(&take_while(|b| *b != b'"'))
+    );
+}
+
+#[test]
+fn it_describe_literals() {
+    assert_eq!(
+        Grammar::Alt(vec![literal("="), literal("=="), literal("=>")]),
+        describe(&literals(&[("=>", 2), ("=", 0), ("==", 1)]))
+    );
+    assert_eq!(Grammar::Terminal("float".to_string()), describe(&float()));
+}
+
+#[test]
+fn it_describe_dynamic_parsers() {
+    assert_eq!(Grammar::Dynamic, describe(&any().bind(returns)));
+    assert_eq!(
+        Grammar::Filter(Box::new(Grammar::Any)),
+        describe(&any().filter(|b| *b > b'a'))
+    );
+    assert_eq!(
+        Grammar::Lookahead(Box::new(literal("a"))),
+        describe(&lookahead('a'))
+    );
+}
+
+#[test]
+fn it_describe_named_rule() {
+    let expr = rule();
+
+    expr.define(
+        '('.then_right(expr.clone())
+            .then_left(')')
+            .or('x')
+            .named("expr"),
+    );
+
+    assert_eq!(
+        Grammar::Named(
+            "expr".to_string(),
+            Box::new(Grammar::Alt(vec![
+                Grammar::Seq(vec![
+                    literal("("),
+                    Grammar::Rule("expr".to_string()),
+                    literal(")")
+                ]),
+                literal("x")
+            ]))
+        ),
+        describe(&expr)
+    );
+}
+
+#[test]
+fn it_describe_anonymous_rule() {
+    let expr = rule();
+
+    expr.define('('.then_right(expr.clone()).or('x'));
+
+    let inner = Grammar::Alt(vec![
+        Grammar::Seq(vec![literal("("), Grammar::Dynamic]),
+        literal("x"),
+    ]);
+
+    assert_eq!(
+        Grammar::Alt(vec![Grammar::Seq(vec![literal("("), inner]), literal("x")]),
+        describe(&expr)
+    );
+}
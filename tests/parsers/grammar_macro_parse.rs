@@ -0,0 +1,65 @@
+extern crate parsecute;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::ebnf::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::literal::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::response::*;
+
+grammar! {
+    struct Lists<'a> {
+        list: usize = cases!(
+            (seq!(('(') ~> (item().optrep()) <~ (')'))) >> |v: Vec<usize>| v.len()
+        );
+        item: usize = list().or(digit().fmap(|_| 0));
+    }
+}
+
+#[test]
+fn it_parse_mutually_recursive_rules() {
+    let g = Lists::new();
+
+    assert_eq!(
+        3,
+        g.list()
+            .execute(b"(1(2)3)", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+    assert_eq!(
+        0,
+        g.item()
+            .execute(b"7", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_compose_grammar_rules() {
+    let g = Lists::new();
+    let p = g.list().clone().then_left(eos());
+
+    assert!(p.execute(b"(1(2)", 0).fold(|_, _, _| false, |_, _| true));
+}
+
+#[test]
+fn it_parse_with_rule_out_of_grammar() {
+    let list = Lists::new().list().clone();
+
+    assert_eq!(
+        (1, 5),
+        list.execute(b"((1))(", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_describe_grammar_rules() {
+    let g = Lists::new();
+
+    assert_eq!(
+        "list ::= \"(\" item* \")\"\nitem ::= list | [0-9]\n",
+        ebnf(g.list(), Notation::W3c)
+    );
+}
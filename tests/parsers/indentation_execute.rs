@@ -0,0 +1,107 @@
+extern crate parsecute;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::core::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::indentation::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::response::*;
+
+#[derive(Debug, PartialEq)]
+enum Tree {
+    Node(String, Vec<Tree>),
+}
+
+fn node(name: &str, children: Vec<Tree>) -> Tree {
+    Tree::Node(name.to_string(), children)
+}
+
+fn ident() -> FMap<TakeWhile, Vec<u8>, String> {
+    take_while(|c| c.is_ascii_lowercase())
+        .fmap_box(Box::new(|v: Vec<u8>| String::from_utf8(v).unwrap()))
+}
+
+fn statement<'a>() -> StatefulParsec<'a, Tree, Vec<usize>> {
+    let parser = ident()
+        .then(':'.then_right(lazy!(block(statement()))).opt())
+        .fmap(|(n, c)| Tree::Node(n, c.unwrap_or_default()));
+
+    StatefulParsec::<'a>(Box::new(parser))
+}
+
+#[test]
+fn it_parse_with_column() {
+    assert_eq!(
+        1,
+        column()
+            .execute(b"ab\ncd", 4)
+            .fold(|c, _, _| c, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_block() {
+    let r = block(statement());
+    let input = b"a:\n  b\n  c:\n    d\n  e\nf";
+
+    assert_eq!(
+        vec![
+            node(
+                "a",
+                vec![
+                    node("b", vec![]),
+                    node("c", vec![node("d", vec![])]),
+                    node("e", vec![])
+                ]
+            ),
+            node("f", vec![])
+        ],
+        r.execute_with(input, 0, &mut Vec::new())
+            .fold(|t, _, _| t, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_block_stopped_by_dedent() {
+    let r = block(statement());
+    let input = b"a:\n    b\n  c";
+
+    assert_eq!(
+        8,
+        r.execute_with(input, 0, &mut Vec::new())
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_block_not_indented() {
+    let r = block(statement());
+    let input = b"a:\nb";
+
+    assert!(r
+        .execute_with(input, 0, &mut Vec::new())
+        .fold(|_, _, _| panic!("Parse error"), |_, c| c,));
+}
+
+#[test]
+fn it_parse_with_aligned() {
+    let r = 'a'.then(aligned('b'));
+
+    assert_eq!(
+        'b',
+        r.execute_with(b"  a\n  b", 2, &mut vec![2])
+            .fold(|(_, b), _, _| b, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_indented_reject() {
+    let r = 'a'.then(indented('b'));
+
+    assert_eq!(
+        3,
+        r.execute_with(b"  a\n  b", 2, &mut vec![2])
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o,)
+    );
+}
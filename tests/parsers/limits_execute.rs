@@ -0,0 +1,102 @@
+extern crate parsecute;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::context::*;
+use parsecute::parsers::core::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::limits::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::response::*;
+
+// Each nesting level executes a Parsec and a Lazy parser
+
+fn nested<'a>() -> Parsec<'a, usize> {
+    let parser = '['
+        .then_right(lazy!(nested()).opt())
+        .then_left(']')
+        .fmap(|v| v.map_or(1, |n| n + 1));
+
+    parsec!('a, parser)
+}
+
+fn limited(limits: Limits) -> Context {
+    Context {
+        limits: Some(limits),
+        ..Context::default()
+    }
+}
+
+#[test]
+fn it_execute_within_limits() {
+    let limits = Limits {
+        depth: 8,
+        fuel: 100,
+    };
+
+    assert_eq!(
+        Ok(3),
+        execute_within(&nested(), b"[[[]]]", 0, limited(limits))
+            .map(|r| r.fold(|a, _, _| a, |_, _| panic!("Parse error")))
+    );
+}
+
+#[test]
+fn it_execute_with_depth_limit() {
+    let limits = Limits {
+        depth: 64,
+        ..Limits::default()
+    };
+    let input = "[".repeat(100_000);
+
+    assert_eq!(
+        Err(ExecutionError::Limit(LimitError::Depth(32))),
+        execute_within(&nested(), input.as_bytes(), 0, limited(limits)).map(|_| ())
+    );
+}
+
+#[test]
+fn it_execute_with_fuel_limit() {
+    let limits = Limits {
+        fuel: 10,
+        ..Limits::default()
+    };
+    let input = "[]".repeat(100);
+    let parser = nested().optrep();
+
+    assert_eq!(
+        Err(ExecutionError::Limit(LimitError::Fuel(7))),
+        execute_within(&parser, input.as_bytes(), 0, limited(limits)).map(|_| ())
+    );
+}
+
+#[test]
+fn it_execute_repeat_with_fuel_limit() {
+    let limits = Limits {
+        fuel: 3,
+        ..Limits::default()
+    };
+    let input = "a".repeat(100);
+
+    let parser = Parsec(Box::new('b'.or('c').or('a'))).optrep();
+
+    assert_eq!(
+        Err(ExecutionError::Limit(LimitError::Fuel(3))),
+        execute_within(&parser, input.as_bytes(), 0, limited(limits)).map(|_| ())
+    );
+    assert_eq!(
+        Ok(Some(100)),
+        execute_within(&'a'.optrep(), input.as_bytes(), 0, limited(limits))
+            .map(|r| r.v.map(|v| v.len()))
+    );
+}
+
+#[test]
+fn it_execute_without_limits() {
+    assert_eq!(
+        (4, None),
+        within(limited(Limits::default()), || nested()
+            .execute(b"[[[[]]]]", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error")))
+    );
+}
@@ -0,0 +1,96 @@
+extern crate parsecute;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::context::*;
+use parsecute::parsers::core::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::lint::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::response::*;
+
+#[test]
+fn it_lint_nullable_repeat() {
+    assert_eq!(vec![Warning::NullableRepeat], lint(&returns(1).optrep()));
+    assert_eq!(vec![Warning::NullableRepeat], lint(&'a'.opt().rep()));
+    assert_eq!(
+        vec![Warning::NullableRepeat],
+        lint(&any().or(eos().fmap(|_| b' ')).rep())
+    );
+}
+
+#[test]
+fn it_lint_consuming_repeat() {
+    assert_eq!(Vec::<Warning>::new(), lint(&any().optrep()));
+    assert_eq!(Vec::<Warning>::new(), lint(&'a'.rep().opt()));
+}
+
+#[test]
+fn it_lint_lazy_and_bind() {
+    assert_eq!(
+        vec![Warning::NullableRepeat],
+        lint(&lazy(|| returns(1)).optrep())
+    );
+    assert_eq!(
+        vec![Warning::NullableRepeat],
+        lint(&returns(1).bind(|_| 'a').optrep())
+    );
+    assert_eq!(
+        vec![Warning::NullableRepeat],
+        lint(&parsec!(returns(1)).optrep())
+    );
+}
+
+fn policy(non_consuming: NonConsuming) -> Context {
+    Context {
+        non_consuming,
+        ..Context::default()
+    }
+}
+
+#[test]
+fn it_execute_non_consuming_repeat() {
+    assert_eq!(
+        0,
+        execute_within(&returns(1).optrep(), b"a", 0, policy(NonConsuming::Stop))
+            .unwrap()
+            .fold(|a, _, _| a.len(), |_, _| panic!("Parse error"))
+    );
+    assert_eq!(
+        3,
+        execute_within(
+            &'a'.opt().many_till('b'),
+            b"aab",
+            0,
+            policy(NonConsuming::Stop)
+        )
+        .unwrap()
+        .fold(|_, o, _| o, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_report_non_consuming_repeat() {
+    assert_eq!(
+        Err(ExecutionError::NonConsuming(0)),
+        execute_within(&returns(1).optrep(), b"a", 0, Context::default()).map(|r| r.v)
+    );
+    assert_eq!(
+        Err(ExecutionError::NonConsuming(1)),
+        execute_within(
+            &'a'.then('b'.opt().rep()),
+            b"ac",
+            0,
+            policy(NonConsuming::Reject)
+        )
+        .map(|r| r.v)
+    );
+}
+
+#[test]
+fn it_reject_non_consuming_repeat_by_default() {
+    assert!(returns(1)
+        .optrep()
+        .execute(b"a", 0)
+        .fold(|_, _, _| false, |_, c| c));
+}
@@ -141,3 +141,44 @@ fn it_parse_extracting_csv_items() {
             .fold(|(_, b), _, _| b.len() + 1, |_, _| panic!("Parse error"),)
     );
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Assign,
+    Eq,
+    Arrow,
+    Not,
+    Neq,
+}
+
+#[test]
+fn it_parse_with_longest_literal() {
+    let r = literals(&[
+        ("=", Operator::Assign),
+        ("==", Operator::Eq),
+        ("=>", Operator::Arrow),
+        ("!", Operator::Not),
+        ("!=", Operator::Neq),
+    ])
+    .optrep();
+
+    assert_eq!(
+        vec![Operator::Eq, Operator::Arrow, Operator::Neq, Operator::Not],
+        r.execute(b"===>!=!", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_literals_rejected() {
+    let r = literals(&[("let", 0), ("letrec", 1)]);
+
+    assert_eq!(
+        (0, 3),
+        r.execute(b"letre", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+    assert!(r
+        .execute(b"le", 0)
+        .fold(|_, _, _| false, |o, c| o == 0 && !c));
+}
@@ -1,8 +1,10 @@
 extern crate parsecute;
 
 use parsecute::parsers::basic::*;
+use parsecute::parsers::context::*;
 use parsecute::parsers::execution::*;
 use parsecute::parsers::flow::*;
+use parsecute::parsers::label::*;
 use parsecute::parsers::monadic::*;
 use parsecute::parsers::response::*;
 
@@ -33,3 +35,153 @@ fn it_parse_any_then_any_macro_seq() {
             .fold(|b, _, _| b as char, |_, _| panic!("Parse error"),)
     );
 }
+
+#[test]
+fn it_parse_destructured_pattern_macro_foreach() {
+    let r = foreach!(
+        (k, v) <- (any().then_left('=').then(any()))
+        yield (k as char, v as char)
+    );
+
+    assert_eq!(
+        ('a', 'b'),
+        r.execute(b"a=b", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_parse_multi_token_pattern_macro_foreach() {
+    let r = foreach!(
+        Point { x, y } <- (any().then_left(',').then(any()).fmap(|(x, y)| Point { x, y }))
+        mut n <- (any()) if (n != b'0')
+        yield {
+            n += x - b'0' + y - b'0';
+            n - b'0'
+        }
+    );
+
+    assert_eq!(
+        6,
+        r.execute(b"1,23", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+    assert_eq!(
+        4,
+        r.execute(b"1,20", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o)
+    );
+}
+
+#[test]
+fn it_parse_local_binding_macro_foreach() {
+    let r = foreach!(
+        a <- (any())
+        let n = a - b'0';
+        b <- (any())
+        let m = b - b'0';
+        yield n * 10 + m
+    );
+
+    assert_eq!(
+        42,
+        r.execute(b"42", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_parse_labelled_guard_macro_foreach() {
+    let r = foreach!(
+        ('\'')
+        b <- (any()) if (b != b'\'') else ("character")
+        ('\'')
+        yield (b)
+    );
+
+    let labels = Context {
+        labels: true,
+        ..Context::default()
+    };
+
+    assert_eq!(
+        Ok(Some(b'b')),
+        execute_within(&r, b"'b'", 0, labels).map(|r| r.v)
+    );
+    assert_eq!(
+        Err(ExecutionError::Failure(Failure {
+            offset: 1,
+            label: "character".to_string()
+        })),
+        execute_within(&r, b"''", 0, labels).map(|r| r.v)
+    );
+}
+
+#[test]
+fn it_parse_flat_tuple_macro_seq() {
+    let r = seq!((any()) ~ (any()) <~ (',') ~ (any()) ~> (any()));
+
+    assert_eq!(
+        (b'a', b'b', b'd'),
+        r.execute(b"ab,cd", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_parse_discarded_items_macro_seq() {
+    let r = seq!(('(') ~> (any()) <~ (')'));
+
+    assert_eq!(
+        b'a',
+        r.execute(b"(a)", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_parse_discard_associativity_macro_seq() {
+    let r = seq!((any()) <~ (any()) ~ (any()));
+
+    assert_eq!(
+        (b'a', b'c'),
+        r.execute(b"abc", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+
+    let r = seq!((any()) <~ (seq!((any()) ~ (any()))));
+
+    assert_eq!(
+        b'a',
+        r.execute(b"abc", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+#[test]
+fn it_parse_named_fields_macro_seq() {
+    let r = seq!(Point { ('(') ~> x: (any()) <~ (',') ~ y: (any()) <~ (')') });
+
+    assert_eq!(
+        Point { x: b'1', y: b'2' },
+        r.execute(b"(1,2)", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_parse_mapped_tuple_macro_seq() {
+    let r = seq!((any()) ~ (any()) ~ (any()) >> |(a, b, c)| a as usize + b as usize + c as usize);
+
+    assert_eq!(
+        3 * b'a' as usize,
+        r.execute(b"aaa", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
@@ -0,0 +1,127 @@
+extern crate parsecute;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::context::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::memo::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::response::*;
+
+fn counted(calls: &Rc<Cell<usize>>) -> Memo<FMap<Any, u8, u8>, u8> {
+    let calls = calls.clone();
+
+    any()
+        .fmap_box(Box::new(move |a| {
+            calls.set(calls.get() + 1);
+            a
+        }))
+        .memo()
+}
+
+fn memoizing() -> Context {
+    Context {
+        memo: true,
+        ..Context::default()
+    }
+}
+
+#[test]
+fn it_parse_with_memo() {
+    let calls = Rc::new(Cell::new(0));
+    let m = counted(&calls);
+    let r = do_try(m.clone().then('x'))
+        .or(do_try(m.clone().then('y')))
+        .or(m.then('z'));
+
+    assert_eq!(
+        (b'a', 'z'),
+        execute_within(&r, b"az", 0, memoizing())
+            .unwrap()
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(1, calls.get());
+}
+
+#[test]
+fn it_parse_with_memo_reject() {
+    let calls = Rc::new(Cell::new(0));
+    let m = counted(&calls).then('x');
+    let r = do_try(m).or(returns((b'_', '_')));
+
+    assert_eq!(
+        (b'_', '_'),
+        execute_within(&r, b"ay", 0, memoizing())
+            .unwrap()
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(1, calls.get());
+}
+
+#[test]
+fn it_parse_with_memo_cleared() {
+    let calls = Rc::new(Cell::new(0));
+    let m = counted(&calls);
+    let input = b"a";
+
+    within(memoizing(), || {
+        m.execute(input, 0);
+        m.execute(input, 0);
+        m.clear();
+        m.execute(input, 0);
+    });
+
+    assert_eq!(2, calls.get());
+}
+
+#[test]
+fn it_parse_with_memo_on_another_input() {
+    let calls = Rc::new(Cell::new(0));
+    let m = counted(&calls);
+
+    execute_within(&m, b"a", 0, memoizing()).unwrap();
+
+    assert_eq!(
+        b'b',
+        execute_within(&m, b"b", 0, memoizing())
+            .unwrap()
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(2, calls.get());
+}
+
+#[test]
+fn it_parse_with_memo_on_modified_input() {
+    let m = 'a'.rep().memo();
+    let mut buffer = b"aaab".to_vec();
+
+    assert_eq!(
+        Some(3),
+        execute_within(&m, &buffer, 0, memoizing())
+            .unwrap()
+            .fold(|_, o, _| Some(o), |_, _| None)
+    );
+
+    buffer.copy_from_slice(b"bbbb");
+
+    assert_eq!(
+        None,
+        execute_within(&m, &buffer, 0, memoizing())
+            .unwrap()
+            .fold(|_, o, _| Some(o), |_, _| None)
+    );
+}
+
+#[test]
+fn it_parse_without_memo_scope() {
+    let calls = Rc::new(Cell::new(0));
+    let m = counted(&calls);
+
+    m.execute(b"a", 0);
+    m.execute(b"a", 0);
+
+    assert_eq!(2, calls.get());
+}
@@ -1,8 +1,24 @@
 pub mod basic_execute;
 pub mod basic_parse;
+pub mod bytes_execute;
+pub mod charset_execute;
+pub mod derive_execute;
+pub mod ebnf_execute;
 pub mod flow_execute;
 pub mod flow_parse;
+pub mod generate_execute;
+pub mod grammar_execute;
+pub mod grammar_macro_parse;
+pub mod indentation_execute;
+pub mod limits_execute;
+pub mod lint_execute;
 pub mod literal_execute;
 pub mod literal_parse;
 pub mod macro_parse;
+pub mod memo_execute;
 pub mod monadic_execute;
+pub mod peg_execute;
+pub mod permutation_execute;
+pub mod railroad_execute;
+pub mod rule_execute;
+pub mod state_execute;
@@ -1,7 +1,9 @@
 extern crate parsecute;
 
 use parsecute::parsers::basic::*;
+use parsecute::parsers::core::*;
 use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
 use parsecute::parsers::monadic::*;
 use parsecute::parsers::response::*;
 
@@ -48,3 +50,66 @@ fn it_parse_with_bind_reject() {
             .fold(|_: u32, _, _| panic!("Parse error"), |_, _| 0,)
     );
 }
+
+#[test]
+fn it_parse_with_fmap_borrowing() {
+    let names = ["zero".to_string(), "one".to_string()];
+    let r = any().fmap(|a| &names[(a - b'0') as usize]);
+
+    assert_eq!(
+        "one",
+        r.execute(b"1", 0)
+            .fold(|a, _, _| a.as_str(), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_fmap_box() {
+    let r = any().fmap_box(Box::new(|a: u8| a as char));
+
+    assert_eq!(
+        'a',
+        r.execute(b"a", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+fn keyword<'a>(keywords: &'a [&'a str]) -> Parsec<'a, usize> {
+    let word = take_while(|c| c.is_ascii_lowercase())
+        .satisfy(move |w| keywords.iter().any(|k| k.as_bytes() == &w[..]))
+        .fmap(move |w| {
+            keywords
+                .iter()
+                .position(|k| k.as_bytes() == &w[..])
+                .unwrap()
+        });
+
+    parsec!('a, word)
+}
+
+#[test]
+fn it_parse_with_borrowed_keywords() {
+    let keywords = ["let", "in"];
+    let r = keyword(&keywords);
+
+    assert_eq!(
+        1,
+        r.execute(b"in", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_bind_box_borrowing() {
+    let closing = [')', ']'];
+    let r = any().bind_box(Box::new(|a| match a {
+        b'(' => closing[0],
+        _ => closing[1],
+    }));
+
+    assert_eq!(
+        2,
+        r.execute(b"[]", 0)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+}
@@ -0,0 +1,152 @@
+extern crate parsecute;
+
+use parsecute::parsers::ebnf::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::peg::*;
+use parsecute::parsers::response::*;
+
+fn leaf(s: &str) -> Tree {
+    Tree::Leaf(s.to_string())
+}
+
+fn node(name: &str, children: Vec<Tree>) -> Tree {
+    Tree::Node(name.to_string(), children)
+}
+
+const EXPR: &str = r#"
+    # Additions and subtractions
+    expr <- term (("+" / "-") term)*
+    term <- [0-9]+
+"#;
+
+#[test]
+fn it_load_peg_grammar() {
+    let p = load(EXPR).unwrap();
+
+    assert_eq!(
+        node(
+            "expr",
+            vec![
+                node("term", vec![leaf("1")]),
+                leaf("+"),
+                node("term", vec![leaf("2"), leaf("3")]),
+            ]
+        ),
+        p.execute(b"1+23", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_load_ebnf_grammar() {
+    let p = load("list = '(', { item }, ')' ; (* nested lists *) item = 'x' | list ;").unwrap();
+
+    assert_eq!(
+        "(x(x))",
+        p.execute(b"(x(x))", 0)
+            .fold(|a, _, _| a.text(), |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_load_grammar_with_lookahead() {
+    let p = load("word <- !'end' [a-z]+ &' '").unwrap();
+
+    assert_eq!(
+        "abc",
+        p.execute(b"abc ", 0)
+            .fold(|a, _, _| a.text(), |_, _| panic!("Parse error"))
+    );
+    assert!(p.execute(b"end ", 0).fold(|_, _, _| false, |_, _| true));
+}
+
+#[test]
+fn it_load_grammar_with_cut() {
+    let backtracking = load("stmt <- 'if' ' ' [a-z]+ / [a-z]+").unwrap();
+    let committed = load("stmt <- 'if' ^ ' ' [a-z]+ / [a-z]+").unwrap();
+
+    assert_eq!(
+        "ifx",
+        backtracking
+            .execute(b"ifx", 0)
+            .fold(|a, _, _| a.text(), |_, _| panic!("Parse error"))
+    );
+    assert!(committed.execute(b"ifx", 0).fold(|_, _, _| false, |_, c| c));
+    assert_eq!(
+        "abc",
+        committed
+            .execute(b"abc", 0)
+            .fold(|a, _, _| a.text(), |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_reject_invalid_grammar() {
+    assert_eq!(
+        Some(LoadError::Undefined("b".to_string())),
+        load("a <- b").err()
+    );
+    assert_eq!(
+        Some(LoadError::Duplicate("a".to_string())),
+        load("a <- 'x'\na <- 'y'").err()
+    );
+    assert_eq!(
+        Some(LoadError::Class("z-a".to_string())),
+        load("a <- [z-a]").err()
+    );
+    assert_eq!(Some(LoadError::Syntax(5)), load("a <- (").err());
+}
+
+#[test]
+fn it_reject_left_recursive_grammar() {
+    assert_eq!(
+        Some(LoadError::LeftRecursion("e".to_string())),
+        load("e <- e '+' 'x' / 'x'").err()
+    );
+    assert_eq!(
+        Some(LoadError::LeftRecursion("a".to_string())),
+        load("a <- 'x'? b / 'y'\nb <- !'z' a 'x'").err()
+    );
+    assert!(load("a <- 'x' a / 'y'").is_ok());
+}
+
+#[test]
+fn it_describe_loaded_grammar() {
+    let p = load(EXPR).unwrap();
+
+    assert_eq!(
+        "expr ::= term ((\"+\" | \"-\") term)*\nterm ::= [0-9]+\n",
+        ebnf(&p, Notation::W3c)
+    );
+}
+
+#[test]
+fn it_load_ebnf_option() {
+    let p = load("a = 'x', [ 'y' ], 'z' ;").unwrap();
+
+    assert_eq!(
+        "xz",
+        p.execute(b"xz", 0)
+            .fold(|a, _, _| a.text(), |_, _| panic!("Parse error"))
+    );
+    assert_eq!(
+        "xyz",
+        p.execute(b"xyz", 0)
+            .fold(|a, _, _| a.text(), |_, _| panic!("Parse error"))
+    );
+}
+
+#[test]
+fn it_load_exported_iso_grammar() {
+    let p = load("list <- '(' (item (',' item)*)? ')'\nitem <- [a-z]+ / list").unwrap();
+    let exported = ebnf(&p, Notation::Iso);
+    let loaded = load(&exported).unwrap();
+
+    assert_eq!(exported, ebnf(&loaded, Notation::Iso));
+    assert_eq!(
+        "(a,(bc,d))",
+        loaded
+            .execute(b"(a,(bc,d))", 0)
+            .fold(|a, _, _| a.text(), |_, _| panic!("Parse error"))
+    );
+}
@@ -0,0 +1,127 @@
+extern crate parsecute;
+
+use parsecute::parsers::context::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::label::*;
+use parsecute::parsers::permutation::*;
+use parsecute::parsers::response::*;
+
+#[test]
+fn it_parse_with_permutation_in_order() {
+    let r = permutation((required('a'), required('b'), optional('c')));
+
+    assert_eq!(
+        ('a', 'b', Some('c')),
+        r.execute(b"abc", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_permutation_any_order() {
+    let r = permutation((required('a'), required('b'), optional('c')));
+
+    assert_eq!(
+        ('a', 'b', Some('c')),
+        r.execute(b"cba", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_permutation_missing_optional() {
+    let r = permutation((required('a'), required('b'), optional('c')));
+
+    assert_eq!(
+        (('a', 'b', None), 2),
+        r.execute(b"ba", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_permutation_missing_required() {
+    let r = permutation((required('a'), required('b'), optional('c')));
+
+    assert_eq!(
+        2,
+        r.execute(b"ca", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o,)
+    );
+}
+
+#[test]
+fn it_parse_with_permutation_duplicate() {
+    let r = permutation((required('a'), required('b'), optional('c')));
+
+    assert_eq!(
+        (3, true),
+        r.execute(b"cbaa", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, c| (o, c),)
+    );
+}
+
+#[test]
+fn it_parse_with_permutation_followed_by_item() {
+    let r = permutation((required('a'), required('b'))).then('a');
+
+    assert_eq!(
+        (2, true),
+        r.execute(b"baa", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, c| (o, c),)
+    );
+}
+
+#[test]
+fn it_parse_with_lenient_permutation_followed_by_item() {
+    let r = lenient_permutation((required('a'), required('b'))).then('a');
+
+    assert_eq!(
+        (('a', 'b'), 'a'),
+        r.execute(b"baa", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_label_permutation_errors() {
+    let r = permutation((required('a'), required('b'), optional('c')));
+    let labels = Context {
+        labels: true,
+        ..Context::default()
+    };
+
+    assert_eq!(
+        Some(ExecutionError::Failure(Failure {
+            offset: 3,
+            label: "duplicate item".to_string()
+        })),
+        execute_within(&r, b"cbaa", 0, labels).err()
+    );
+    assert_eq!(
+        Some(ExecutionError::Failure(Failure {
+            offset: 2,
+            label: "missing item".to_string()
+        })),
+        execute_within(&r, b"ca", 0, labels).err()
+    );
+}
+
+#[test]
+fn it_parse_with_permutation_of_attributes() {
+    let attribute = |name: &str| {
+        (name.to_string() + "=")
+            .then_right(take_until(";"))
+            .then_left(';')
+    };
+    let r = permutation((required(attribute("id")), optional(attribute("class"))));
+
+    assert_eq!(
+        (b"main".to_vec(), Some(b"red".to_vec())),
+        r.execute(b"class=red;id=main;", 0).fold(
+            |(a, b), _, _| (a.to_vec(), b.map(|b| b.to_vec())),
+            |_, _| panic!("Parse error"),
+        )
+    );
+}
@@ -0,0 +1,61 @@
+extern crate parsecute;
+
+use parsecute::parsers::flow::*;
+use parsecute::parsers::grammar::*;
+use parsecute::parsers::literal::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::railroad::*;
+use parsecute::parsers::rule::*;
+
+#[test]
+fn it_draw_literal() {
+    let svg = diagram(&describe(&'a'));
+
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"108\" height=\"62\"")
+    );
+    assert!(svg.contains("<rect class=\"terminal\" x=\"40\" y=\"20\" width=\"28\" height=\"22\""));
+    assert!(svg.contains("<text x=\"54\" y=\"36\">a</text>"));
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn it_draw_sequence_choice_and_loop() {
+    let svg = diagram(&describe(&'a'.then('b'.or('c')).then('d'.optrep())));
+
+    for text in &["a", "b", "c", "d"] {
+        assert!(svg.contains(&format!(">{}</text>", text)));
+    }
+
+    assert_eq!(4, svg.matches("<rect class=\"terminal\"").count());
+    assert_eq!(3, svg.matches("0 0 1 10 10v").count());
+}
+
+#[test]
+fn it_draw_escaped_text() {
+    let svg = diagram(&describe(&"<&>"));
+
+    assert!(svg.contains(">&lt;&amp;&gt;</text>"));
+}
+
+#[test]
+fn it_draw_one_diagram_per_rule() {
+    let list = rule();
+
+    list.define(
+        '('.then_right(list.clone().optrep())
+            .then_left(')')
+            .fmap(|v: Vec<usize>| v.len())
+            .or(digit().fmap(|_| 0).named("digit"))
+            .named("list"),
+    );
+
+    let diagrams = railroad(&list);
+    let names: Vec<&str> = diagrams.iter().map(|(name, _)| name.as_str()).collect();
+
+    assert_eq!(vec!["list", "digit"], names);
+    assert!(diagrams[0].1.contains("<rect class=\"nonterminal\""));
+    assert!(diagrams[0].1.contains(">list</text>"));
+    assert!(diagrams[1].1.contains("<rect class=\"special\""));
+    assert!(diagrams[1].1.contains(">[0-9]</text>"));
+}
@@ -0,0 +1,271 @@
+extern crate parsecute;
+
+use std::rc::Rc;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::core::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::literal::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::response::*;
+use parsecute::parsers::rule::*;
+
+fn number() -> FMap<Repeat<std::ops::Range<char>, char>, Vec<char>, i32> {
+    digit().rep().fmap_box(Box::new(|v: Vec<char>| {
+        v.iter().collect::<String>().parse().unwrap()
+    }))
+}
+
+#[test]
+fn it_parse_with_left_recursion() {
+    let r = left_rec(|expr| {
+        expr.then_left('-')
+            .then(number())
+            .fmap(|(a, b)| a - b)
+            .or(number())
+    });
+
+    assert_eq!(
+        (5, 6),
+        r.execute(b"10-3-2", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_left_recursion_seed_only() {
+    let r = left_rec(|expr| {
+        expr.then_left('-')
+            .then(number())
+            .fmap(|(a, b)| a - b)
+            .or(number())
+    });
+
+    assert_eq!(
+        (10, 2),
+        r.execute(b"10+3", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_left_recursion_reject() {
+    let r = left_rec(|expr| {
+        expr.then_left('-')
+            .then(number())
+            .fmap(|(a, b)| a - b)
+            .or(number())
+    });
+
+    assert_eq!(
+        0,
+        r.execute(b"-3", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o,)
+    );
+}
+
+#[test]
+fn it_parse_with_indirect_left_recursion() {
+    let r = left_rec(|a| {
+        let b = a
+            .then_left('y')
+            .fmap(|s: String| s + "y")
+            .or('b'.fmap(|c| c.to_string()));
+
+        b.then_left('x')
+            .fmap(|s| s + "x")
+            .or('a'.fmap(|c| c.to_string()))
+    });
+
+    assert_eq!(
+        "ayxyx",
+        r.execute(b"ayxyx", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+// a ::= b 'x' | 'a' and b ::= a 'y' | 'b' where a is the head of the left recursive cycle
+
+#[test]
+fn it_parse_with_mutual_left_recursive_rules() {
+    let b = rule();
+    let a = left_rec(|_| {
+        b.clone()
+            .then_left('x')
+            .fmap(|s: String| s + "x")
+            .or('a'.fmap(|c| c.to_string()))
+    });
+
+    b.define(
+        a.clone()
+            .then_left('y')
+            .fmap(|s: String| s + "y")
+            .or('b'.fmap(|c| c.to_string())),
+    );
+
+    assert_eq!(
+        "ayxyx",
+        a.execute(b"ayxyx", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(
+        "bxyxy",
+        b.execute(b"bxyxy", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_shared_left_recursive_rule() {
+    let expr = left_rec(|expr| {
+        expr.then_left('-')
+            .then(number())
+            .fmap(|(a, b)| a - b)
+            .or(number())
+    });
+    let r = expr.clone().then_left(',').then(expr);
+
+    assert_eq!(
+        (1, 2),
+        r.execute(b"3-2,5-3", 0)
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_rule() {
+    let nested = rule();
+
+    nested.define(
+        '('.then_right(nested.clone().opt())
+            .then_left(')')
+            .fmap(|v: Option<i32>| v.map_or(1, |n| n + 1)),
+    );
+
+    assert_eq!(
+        (3, 6),
+        nested
+            .execute(b"((()))", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_mutual_rules() {
+    let even = rule();
+    let odd = rule();
+
+    even.define(
+        'a'.then_right(odd.clone())
+            .fmap(|n: i32| n + 1)
+            .or(returns(0)),
+    );
+    odd.define('a'.then_right(even.clone()).fmap(|n: i32| n + 1));
+
+    assert_eq!(
+        (4, 4),
+        even.execute(b"aaaab", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+    assert!(even.execute(b"aaab", 0).fold(|_, _, _| false, |_, c| c));
+}
+
+fn built<'a>() -> Parsec<'a, usize> {
+    let nested = rule();
+
+    nested.define(
+        '('.then_right(nested.clone().opt())
+            .then_left(')')
+            .fmap(|v: Option<usize>| v.map_or(1, |n| n + 1)),
+    );
+
+    Parsec(Box::new(nested.clone()))
+}
+
+#[test]
+fn it_parse_with_rule_out_of_scope() {
+    assert_eq!(
+        (2, 4),
+        built()
+            .execute(b"(())", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_reject_with_undefined_rule() {
+    let undefined = rule::<char>();
+
+    assert_eq!(
+        0,
+        undefined
+            .execute(b"a", 0)
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o,)
+    );
+}
+
+#[test]
+fn it_parse_with_dropped_rule() {
+    let reference = {
+        let dropped = rule::<char>();
+        let reference = dropped.clone();
+        dropped.define('a');
+        reference
+    };
+
+    assert_eq!(
+        ('a', 1),
+        reference
+            .execute(b"a", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+// expr ::= term '+' expr | term and term ::= '(' expr ')' | digit where term is defined last
+// and dropped when the function returns
+
+fn forward<'a>(counter: Rc<()>) -> Rule<'a, u32> {
+    let expr = rule();
+    let term = rule();
+
+    expr.define(
+        do_try(term.clone().then_left('+'))
+            .then(expr.clone())
+            .fmap(|(a, b)| a + b)
+            .or(term.clone()),
+    );
+    term.define(
+        '('.then_right(expr.clone())
+            .then_left(')')
+            .or(digit().fmap(move |c| {
+                let _ = &counter;
+                c.to_digit(10).unwrap()
+            })),
+    );
+
+    expr
+}
+
+#[test]
+fn it_parse_with_forward_rule_out_of_scope() {
+    assert_eq!(
+        (6, 7),
+        forward(Rc::new(()))
+            .execute(b"(1+2)+3", 0)
+            .fold(|a, o, _| (a, o), |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_drop_unreferenced_rules() {
+    let counter = Rc::new(());
+    let expr = forward(counter.clone());
+    let reference = expr.clone();
+
+    drop(expr);
+    assert_eq!(2, Rc::strong_count(&counter));
+
+    drop(reference);
+    assert_eq!(1, Rc::strong_count(&counter));
+}
@@ -0,0 +1,139 @@
+extern crate parsecute;
+
+use parsecute::parsers::basic::*;
+use parsecute::parsers::core::*;
+use parsecute::parsers::execution::*;
+use parsecute::parsers::flow::*;
+use parsecute::parsers::monadic::*;
+use parsecute::parsers::permutation::*;
+use parsecute::parsers::response::*;
+use parsecute::parsers::state::*;
+
+fn ident() -> FMap<TakeWhile, Vec<u8>, String> {
+    take_while(|c| c.is_ascii_lowercase())
+        .fmap_box(Box::new(|v: Vec<u8>| String::from_utf8(v).unwrap()))
+}
+
+#[test]
+fn it_parse_with_map_state() {
+    let r = ident().map_state(|n, st: &mut Vec<String>| st.push(n));
+    let mut state = Vec::new();
+
+    assert_eq!(
+        3,
+        r.execute_with(b"foo", 0, &mut state)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(vec!["foo".to_string()], state);
+}
+
+#[test]
+fn it_parse_with_filter_state() {
+    let typedef = "typedef "
+        .then_right(ident())
+        .map_state(|n, st: &mut Vec<String>| st.push(n));
+    let type_name = ident().filter_state(|n, st: &Vec<String>| st.contains(n));
+    let r = typedef.then_left(';').then_right(type_name);
+
+    assert_eq!(
+        "foo",
+        r.execute_with(b"typedef foo;foo", 0, &mut Vec::new())
+            .fold(|a, _, _| a, |_, _| panic!("Parse error"),)
+    );
+}
+
+#[test]
+fn it_parse_with_filter_state_reject() {
+    let type_name = ident().filter_state(|n, st: &Vec<String>| st.contains(n));
+
+    assert_eq!(
+        3,
+        type_name
+            .execute_with(b"bar", 0, &mut vec!["foo".to_string()])
+            .fold(|_, _, _| panic!("Parse error"), |o, _| o,)
+    );
+}
+
+#[test]
+fn it_parse_with_state_restored_on_try() {
+    let count = || "ab".map_state(|_, st: &mut u32| *st += 1);
+    let r = do_try(count().then('x')).or(count().then('y'));
+    let mut state = 0;
+
+    assert_eq!(
+        3,
+        r.execute_with(b"aby", 0, &mut state)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(1, state);
+}
+
+#[test]
+fn it_parse_with_state_through_repeat() {
+    let r = 'a'.map_state(|_, st: &mut u32| *st += 1).optrep();
+    let mut state = 0;
+
+    assert_eq!(
+        4,
+        r.execute_with(b"aaaab", 0, &mut state)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(4, state);
+}
+
+fn nested<'a>() -> StatefulParsec<'a, (), usize> {
+    let parser = '('
+        .map_state(|_, depth: &mut usize| *depth += 1)
+        .then(lazy!(nested()).opt())
+        .then(')')
+        .fmap(|_| ());
+
+    StatefulParsec::<'a>(Box::new(parser))
+}
+
+#[test]
+fn it_parse_with_stateful_parsec() {
+    let mut state = 0;
+
+    assert_eq!(
+        6,
+        nested()
+            .execute_with(b"((()))", 0, &mut state)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(3, state);
+}
+
+#[test]
+fn it_parse_with_state_and_boxed_choices() {
+    let count = || 'x'.map_state(|_, st: &mut u32| *st += 1);
+    let choice = choice_vec(vec![parsec!("ab"), parsec!("ac")]);
+    let table = dispatch(vec![(b"b", parsec!('b'))], parsec!('c'));
+    let r = count().then(choice).then(count()).then(table);
+    let mut state = 0;
+
+    assert_eq!(
+        5,
+        r.execute_with(b"xacxc", 0, &mut state)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(2, state);
+}
+
+#[test]
+fn it_parse_with_state_and_permutation() {
+    let push = |c: char, st: &mut Vec<char>| st.push(c);
+    let r = permutation((
+        required('b'.map_state(push)),
+        optional(returns('z').map_state(push).then('x')),
+    ))
+    .then('a'.map_state(push));
+    let mut state = Vec::new();
+
+    assert_eq!(
+        2,
+        r.execute_with(b"ba", 0, &mut state)
+            .fold(|_, o, _| o, |_, _| panic!("Parse error"),)
+    );
+    assert_eq!(vec!['b', 'a'], state);
+}